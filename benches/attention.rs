@@ -0,0 +1,5 @@
+use burn_benches::{attention, bench::BenchSuite};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, attention::AttentionBenchSuite::run);
+criterion_main!(benches);
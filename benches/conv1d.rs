@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, conv1d};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, conv1d::Conv1dBenchSuite::run);
+criterion_main!(benches);
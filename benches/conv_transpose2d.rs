@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, conv_transpose2d};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, conv_transpose2d::ConvTranspose2dBenchSuite::run);
+criterion_main!(benches);
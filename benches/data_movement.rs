@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, data_movement};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, data_movement::DataMovementBenchSuite::run);
+criterion_main!(benches);
@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, decoder};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, decoder::DecoderBenchSuite::run);
+criterion_main!(benches);
@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, elementwise};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, elementwise::ElementwiseBenchSuite::run);
+criterion_main!(benches);
@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, matmul};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, matmul::MatmulBenchSuite::run);
+criterion_main!(benches);
@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, models};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, models::ModelsBenchSuite::run);
+criterion_main!(benches);
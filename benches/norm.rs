@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, norm};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, norm::NormBenchSuite::run);
+criterion_main!(benches);
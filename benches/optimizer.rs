@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, optimizer};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, optimizer::OptimizerBenchSuite::run);
+criterion_main!(benches);
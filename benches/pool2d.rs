@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, pool2d};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, pool2d::Pool2dBenchSuite::run);
+criterion_main!(benches);
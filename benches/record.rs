@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, record};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, record::RecordBenchSuite::run);
+criterion_main!(benches);
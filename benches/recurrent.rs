@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, recurrent};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, recurrent::RecurrentBenchSuite::run);
+criterion_main!(benches);
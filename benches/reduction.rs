@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, reduction};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, reduction::ReductionBenchSuite::run);
+criterion_main!(benches);
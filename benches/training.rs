@@ -0,0 +1,5 @@
+use burn_benches::{bench::BenchSuite, training};
+use criterion::{criterion_group, criterion_main};
+
+criterion_group!(benches, training::TrainingBenchSuite::run);
+criterion_main!(benches);
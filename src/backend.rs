@@ -0,0 +1,179 @@
+use crate::bench::BenchSuite;
+use criterion::Criterion;
+
+/// Environment variable selecting the backend used by the benchmarks.
+///
+/// The value is the cargo feature of the backend, e.g. `ndarray-blas-openblas` or `wgpu`. The
+/// first enabled backend is used when it isn't set.
+pub static BACKEND_ENV: &str = "BURN_BENCH_BACKEND";
+
+/// The backends enabled by the cargo features, which can be selected at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendKind {
+    #[cfg(any(
+        feature = "ndarray",
+        feature = "ndarray-blas-netlib",
+        feature = "ndarray-blas-openblas",
+        feature = "ndarray-no-std"
+    ))]
+    Ndarray,
+    #[cfg(feature = "tch-cpu")]
+    TchCpu,
+    #[cfg(feature = "tch-gpu")]
+    TchGpu,
+    #[cfg(feature = "wgpu")]
+    Wgpu,
+}
+
+impl BackendKind {
+    /// All the backends enabled by the cargo features.
+    pub fn enabled() -> Vec<Self> {
+        vec![
+            #[cfg(any(
+                feature = "ndarray",
+                feature = "ndarray-blas-netlib",
+                feature = "ndarray-blas-openblas",
+                feature = "ndarray-no-std"
+            ))]
+            Self::Ndarray,
+            #[cfg(feature = "tch-cpu")]
+            Self::TchCpu,
+            #[cfg(feature = "tch-gpu")]
+            Self::TchGpu,
+            #[cfg(feature = "wgpu")]
+            Self::Wgpu,
+        ]
+    }
+
+    /// The backend selected by the [environment variable](BACKEND_ENV).
+    pub fn selected() -> Result<Self, BackendError> {
+        let enabled = Self::enabled();
+
+        match std::env::var(BACKEND_ENV) {
+            Ok(name) => enabled
+                .iter()
+                .find(|kind| kind.matches(&name))
+                .copied()
+                .ok_or(BackendError::NotEnabled { name, enabled }),
+            Err(_) => enabled.first().copied().ok_or(BackendError::NoneEnabled),
+        }
+    }
+
+    /// Whether the backend is selected by the given cargo feature.
+    fn matches(self, feature: &str) -> bool {
+        match self {
+            #[cfg(any(
+                feature = "ndarray",
+                feature = "ndarray-blas-netlib",
+                feature = "ndarray-blas-openblas",
+                feature = "ndarray-no-std"
+            ))]
+            Self::Ndarray => feature.starts_with("ndarray"),
+            #[cfg(feature = "tch-cpu")]
+            Self::TchCpu => feature == "tch-cpu",
+            #[cfg(feature = "tch-gpu")]
+            Self::TchGpu => feature == "tch-gpu",
+            #[cfg(feature = "wgpu")]
+            Self::Wgpu => feature == "wgpu",
+        }
+    }
+
+    /// The flag identifying the backend in the benchmark ids.
+    pub fn flag(self) -> &'static str {
+        match self {
+            #[cfg(any(
+                feature = "ndarray",
+                feature = "ndarray-blas-netlib",
+                feature = "ndarray-blas-openblas",
+                feature = "ndarray-no-std"
+            ))]
+            Self::Ndarray => {
+                if cfg!(feature = "ndarray-blas-openblas") {
+                    "ndarray-openblas"
+                } else if cfg!(feature = "ndarray-blas-netlib") {
+                    "ndarray-netlib"
+                } else if cfg!(feature = "ndarray") {
+                    "ndarray"
+                } else {
+                    "ndarray-no-std"
+                }
+            }
+            #[cfg(feature = "tch-cpu")]
+            Self::TchCpu => "tch-cpu",
+            #[cfg(feature = "tch-gpu")]
+            Self::TchGpu => "tch-gpu",
+            #[cfg(feature = "wgpu")]
+            Self::Wgpu => "wgpu",
+        }
+    }
+}
+
+/// The selected backend can't be used.
+#[derive(Debug)]
+pub enum BackendError {
+    /// The backend isn't enabled by the cargo features of the build.
+    NotEnabled {
+        name: String,
+        enabled: Vec<BackendKind>,
+    },
+    /// No backend feature is enabled.
+    NoneEnabled,
+}
+
+impl std::fmt::Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::NotEnabled { name, enabled } => {
+                let enabled: Vec<_> = enabled.iter().map(|kind| kind.flag()).collect();
+
+                write!(
+                    f,
+                    "backend '{name}' isn't enabled, available backends: {}",
+                    enabled.join(", ")
+                )
+            }
+            BackendError::NoneEnabled => f.write_str("no backend feature is enabled"),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+/// Run the suite on the backend selected at runtime.
+///
+/// The benchmarks exit with an error when the backend can't be selected, which is reported by the
+/// CLI for the parameter using that backend.
+pub fn run_suite<S: BenchSuite>(c: &mut Criterion) {
+    let kind = match BackendKind::selected() {
+        Ok(kind) => kind,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    };
+
+    match kind {
+        #[cfg(any(
+            feature = "ndarray",
+            feature = "ndarray-blas-netlib",
+            feature = "ndarray-blas-openblas",
+            feature = "ndarray-no-std"
+        ))]
+        BackendKind::Ndarray => {
+            S::run_backend::<burn_ndarray::NdArray<f32>>(c, &burn_ndarray::NdArrayDevice::Cpu)
+        }
+        #[cfg(feature = "tch-cpu")]
+        BackendKind::TchCpu => {
+            S::run_backend::<burn_tch::LibTorch<f32>>(c, &burn_tch::LibTorchDevice::Cpu)
+        }
+        #[cfg(feature = "tch-gpu")]
+        BackendKind::TchGpu => {
+            S::run_backend::<burn_tch::LibTorch<f32>>(c, &burn_tch::LibTorchDevice::Cuda(0))
+        }
+        #[cfg(feature = "wgpu")]
+        BackendKind::Wgpu => S::run_backend::<burn_wgpu::Wgpu<burn_wgpu::Vulkan, f32, i32>>(
+            c,
+            &burn_wgpu::WgpuDevice::DiscreteGpu(0),
+        ),
+    }
+}
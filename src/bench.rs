@@ -1,42 +1,422 @@
 use std::time::Duration;
 
-use criterion::{black_box, BenchmarkId, Criterion};
+use burn::backend::Autodiff;
+use burn::config::Config;
+use burn::module::AutodiffModule;
+use burn::nn::loss::{MseLoss, Reduction};
+use burn::optim::{GradientsParams, Optimizer};
+use burn::tensor::backend::{AutodiffBackend, Backend};
+use burn::tensor::{Distribution, Tensor};
+use criterion::measurement::WallTime;
+use criterion::{black_box, BatchSize, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+use serde::Serialize;
 
-use crate::bench_id;
+use crate::{backend::run_suite, bench_id, config_file::configs_from_env, stable_hash};
 
 pub trait BenchSuite {
+    type Config: Config;
+
     fn name() -> String;
-    fn details() -> String;
-    fn run(c: &mut Criterion);
+    /// What the suite benchmarks, introducing its configs in the [details](BenchSuite::details).
+    fn description() -> String;
+    /// The description of the suite followed by the id, the swept values and the content of each
+    /// config.
+    fn details() -> String {
+        let mut details = Self::description() + "\n\n";
+
+        Self::configs().into_iter().for_each(|case| {
+            let sweep = case
+                .sweep
+                .map(|sweep| format!(" ({sweep})"))
+                .unwrap_or_default();
+            details += format!("- {}{sweep} => `{}`\n", case.id, case.config).as_str()
+        });
+
+        details
+    }
+    /// The configs benchmarked when the config file doesn't list the suite.
+    fn default_configs() -> Vec<Self::Config>;
+    /// Short and readable summary of the config, e.g. `b32-l4-d1024`.
+    fn label(config: &Self::Config) -> String;
+    /// Name of the [elements](Work::Elements) processed by the workloads, e.g. `token`.
+    fn element() -> Option<&'static str> {
+        None
+    }
+    /// Check that the config is supported by the workloads, returning the reason otherwise.
+    fn validate(_config: &Self::Config) -> Result<(), String> {
+        Ok(())
+    }
+    /// The configs benchmarked by the suite, read from the [config file](crate::config_file) when
+    /// one is given.
+    ///
+    /// The swept configs are identified like the others and keep their swept values for display.
+    ///
+    /// # Panics
+    ///
+    /// If a config isn't [supported](BenchSuite::validate), which the CLI checks beforehand.
+    fn configs() -> Vec<BenchCase<Self::Config>> {
+        configs_from_env(&Self::name())
+            .unwrap_or_else(|| {
+                Self::default_configs()
+                    .into_iter()
+                    .map(|config| (None, config))
+                    .collect()
+            })
+            .into_iter()
+            .map(|(sweep, config)| {
+                let label = Self::label(&config);
+
+                if let Err(reason) = Self::validate(&config) {
+                    panic!(
+                        "Unsupported config '{}' for suite '{}': {reason}",
+                        sweep.as_ref().unwrap_or(&label),
+                        Self::name()
+                    );
+                }
+
+                BenchCase {
+                    sweep,
+                    ..BenchCase::identified(&label, config)
+                }
+            })
+            .collect()
+    }
+    /// Run the suite on the given backend.
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device);
+    /// Run the suite on the backend selected at runtime.
+    fn run(c: &mut Criterion)
+    where
+        Self: Sized,
+    {
+        run_suite::<Self>(c);
+    }
+}
+
+/// A config benchmarked by a suite, along with its id in the benchmark names.
+#[derive(Debug, Clone)]
+pub struct BenchCase<C> {
+    pub id: String,
+    /// The values of the swept fields, e.g. `batch_size=32`, when the config comes from a sweep.
+    pub sweep: Option<String>,
+    pub config: C,
 }
 
-pub trait Bench {
+impl<C: Serialize> BenchCase<C> {
+    /// Identify the config by its [label](BenchSuite::label) and a hash of its content, formatted
+    /// as `{label}-{hash}`.
+    ///
+    /// The id only depends on the config, so it stays the same when the configs of the suite
+    /// change or when the config is swept, and two configs with the same label still get
+    /// different ids.
+    pub fn identified(label: &str, config: C) -> Self {
+        let content = serde_json::to_string(&config).expect("Configs should be serializable");
+        let hash = stable_hash(content.as_bytes()) as u32;
+
+        Self {
+            id: format!("{label}-{hash:08x}"),
+            sweep: None,
+            config,
+        }
+    }
+}
+
+/// A workload written once for any backend.
+pub trait Bench<B: Backend> {
     type Config: std::fmt::Display;
 
-    fn prepare(&self, config: &Self::Config) -> BenchFunc;
+    /// Prepare the inputs of the workload, returning the function executed at each iteration.
+    ///
+    /// The function returns the output of the workload, flattened, so the runner can finish the
+    /// iteration, e.g. by executing the backward pass.
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B>;
+
+    /// The work done by the forward passes of one iteration, reported as its throughput.
+    fn work(&self, _config: &Self::Config) -> Option<Work> {
+        None
+    }
+
+    /// Number of passes of the workload executed at each iteration.
+    fn passes(&self, _config: &Self::Config) -> usize {
+        1
+    }
+
+    /// The tensor the backward pass starts from, the flattened output by default.
+    fn loss(&self, output: Tensor<B, 1>) -> Tensor<B, 1> {
+        output
+    }
+
+    /// Whether the workload has a gradient, the other configs being skipped by the
+    /// [backward](run_benchmark_backward) benchmarks.
+    fn differentiable(&self, _config: &Self::Config) -> bool {
+        true
+    }
 }
 
-pub type BenchBoxed<C> = Box<dyn Bench<Config = C>>;
-pub type BenchFunc = Box<dyn FnMut()>;
+/// The analytic amount of work done by one iteration of a workload.
+#[derive(Debug, Clone, Copy)]
+pub enum Work {
+    /// Floating point operations, for compute-bound workloads.
+    Flops(u64),
+    /// Bytes read and written, for memory-bound workloads.
+    Bytes(u64),
+    /// Elements processed, named by the [suite](BenchSuite::element), e.g. generated tokens.
+    Elements(u64),
+}
 
-pub fn run_benchmark<C, B>(c: &mut Criterion, name: &str, configs: Vec<C>, bench: B)
-where
+impl Work {
+    /// Scale the floating point operations.
+    ///
+    /// The memory traffic isn't estimated beyond the forward pass and the number of processed
+    /// elements stays the same, so they aren't scaled.
+    fn scale(self, factor: u64) -> Self {
+        match self {
+            Work::Flops(flops) => Work::Flops(flops * factor),
+            work => work,
+        }
+    }
+}
+
+/// Criterion has no unit for the floating point operations, so they are reported as elements,
+/// like the [named elements](BenchSuite::element). The JSON messages don't tell them apart: the
+/// [report](crate::report) counts the elements as operations unless the suite names them.
+impl From<Work> for Throughput {
+    fn from(work: Work) -> Self {
+        match work {
+            Work::Flops(flops) => Throughput::Elements(flops),
+            Work::Bytes(bytes) => Throughput::Bytes(bytes),
+            Work::Elements(elements) => Throughput::Elements(elements),
+        }
+    }
+}
+
+/// A workload whose iterations consume a state, e.g. a cache filled by the previous steps.
+///
+/// The state is created before each iteration and its creation isn't measured.
+pub trait BatchedBench<B: Backend> {
+    type Config: std::fmt::Display;
+    type State: 'static;
+
+    /// Prepare the inputs of the workload, returning the function creating the state of each
+    /// iteration and the function executed at each iteration.
+    fn prepare(
+        &self,
+        config: &Self::Config,
+        device: &B::Device,
+    ) -> (SetupFunc<Self::State>, RoutineFunc<B, Self::State>);
+
+    /// The work done by one iteration of the workload, reported as its throughput.
+    fn work(&self, _config: &Self::Config) -> Option<Work> {
+        None
+    }
+}
+
+pub type BenchBoxed<B, C> = Box<dyn Bench<B, Config = C>>;
+pub type BenchFunc<B> = Box<dyn FnMut() -> Tensor<B, 1>>;
+pub type SetupFunc<S> = Box<dyn FnMut() -> S>;
+pub type RoutineFunc<B, S> = Box<dyn FnMut(S) -> Tensor<B, 1>>;
+
+/// Register the variants of the workload: the forward pass on the backend, named `{name}`,
+/// and the forward and backward passes on the autodiff backend, named `{name}-autodiff`.
+pub fn run_benchmark_variants<B, C, W>(
+    c: &mut Criterion,
+    name: &str,
+    cases: &[BenchCase<C>],
+    bench: &W,
+    device: &B::Device,
+) where
+    B: Backend,
     C: std::fmt::Display,
-    B: Bench<Config = C>,
+    W: Bench<B, Config = C> + Bench<Autodiff<B>, Config = C>,
 {
-    let mut group = c.benchmark_group(name);
-    group.sample_size(10);
-    group.warm_up_time(Duration::from_millis(250));
-    group.measurement_time(Duration::from_millis(250));
+    run_benchmark::<B, C, W>(c, name, cases, bench, device);
+    run_benchmark_backward::<Autodiff<B>, C, W>(
+        c,
+        &format!("{name}-autodiff"),
+        cases,
+        bench,
+        device,
+    );
+}
+
+/// Benchmark the forward pass of the workload.
+pub fn run_benchmark<B, C, W>(
+    c: &mut Criterion,
+    name: &str,
+    cases: &[BenchCase<C>],
+    bench: &W,
+    device: &B::Device,
+) where
+    B: Backend,
+    C: std::fmt::Display,
+    W: Bench<B, Config = C>,
+{
+    let mut group = benchmark_group(c, name);
 
-    configs.iter().enumerate().for_each(|(i, config)| {
-        let mut func = bench.prepare(config);
+    cases.iter().for_each(|case| {
+        let mut func = bench.prepare(&case.config, device);
+        let passes = bench.passes(&case.config);
 
-        group.bench_with_input(BenchmarkId::new(bench_id(), i + 1), &(), |b, _i| {
+        if let Some(work) = bench.work(&case.config) {
+            group.throughput(work.into());
+        }
+
+        group.bench_with_input(BenchmarkId::new(bench_id(), &case.id), &(), |b, _i| {
             b.iter(|| {
-                func();
-                black_box(())
+                let mut output = func();
+                for _ in 1..passes {
+                    output = func();
+                }
+                B::sync(device);
+                black_box(output)
             })
         });
     });
 }
+
+/// Benchmark the forward and backward passes of the workload.
+///
+/// The backward pass is estimated to do twice the operations of the forward pass. The backend is
+/// synchronized after each backward pass, and the configs without a
+/// [gradient](Bench::differentiable) aren't benchmarked.
+pub fn run_benchmark_backward<B, C, W>(
+    c: &mut Criterion,
+    name: &str,
+    cases: &[BenchCase<C>],
+    bench: &W,
+    device: &B::Device,
+) where
+    B: AutodiffBackend,
+    C: std::fmt::Display,
+    W: Bench<B, Config = C>,
+{
+    let mut group = benchmark_group(c, name);
+
+    cases
+        .iter()
+        .filter(|case| bench.differentiable(&case.config))
+        .for_each(|case| {
+            let mut func = bench.prepare(&case.config, device);
+            let passes = bench.passes(&case.config);
+
+            if let Some(work) = bench.work(&case.config) {
+                group.throughput(work.scale(3).into());
+            }
+
+            group.bench_with_input(BenchmarkId::new(bench_id(), &case.id), &(), |b, _i| {
+                b.iter(|| {
+                    for _ in 0..passes {
+                        let grads = bench.loss(func()).backward();
+                        B::sync(device);
+                        black_box(grads);
+                    }
+                })
+            });
+        });
+}
+
+/// Benchmark the workload, excluding the creation of the state of each iteration.
+pub fn run_benchmark_batched<B, C, W>(
+    c: &mut Criterion,
+    name: &str,
+    cases: &[BenchCase<C>],
+    bench: &W,
+    device: &B::Device,
+) where
+    B: Backend,
+    C: std::fmt::Display,
+    W: BatchedBench<B, Config = C>,
+{
+    let mut group = benchmark_group(c, name);
+
+    cases.iter().for_each(|case| {
+        let (mut setup, mut routine) = bench.prepare(&case.config, device);
+
+        if let Some(work) = bench.work(&case.config) {
+            group.throughput(work.into());
+        }
+
+        group.bench_with_input(BenchmarkId::new(bench_id(), &case.id), &(), |b, _i| {
+            b.iter_batched(
+                || {
+                    let state = setup();
+                    B::sync(device);
+                    state
+                },
+                |state| {
+                    let output = routine(state);
+                    B::sync(device);
+                    black_box(output)
+                },
+                BatchSize::PerIteration,
+            )
+        });
+    });
+}
+
+/// The function executing a training step of the model: the forward pass, the mean squared error
+/// against a random target shaped like the output, the backward pass and the update of the
+/// optimizer.
+///
+/// The model is updated for the next iteration and the loss is returned.
+pub fn training_step<B, M, O, F, const D: usize>(
+    model: M,
+    mut optimizer: O,
+    learning_rate: f64,
+    forward: F,
+) -> BenchFunc<B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B> + 'static,
+    O: Optimizer<M, B> + 'static,
+    F: Fn(&M) -> Tensor<B, D> + 'static,
+{
+    let output = forward(&model);
+    let target = Tensor::random(output.dims(), Distribution::Default).to_device(&output.device());
+    let mse = MseLoss::new();
+    let mut model = Some(model);
+
+    Box::new(move || {
+        let current = model
+            .take()
+            .expect("The model should be returned by the previous step");
+        let loss = mse.forward(forward(&current), target.clone(), Reduction::Mean);
+        let grads = GradientsParams::from_grads(loss.backward(), &current);
+        model = Some(optimizer.step(learning_rate, current, grads));
+
+        loss
+    })
+}
+
+fn benchmark_group<'a>(c: &'a mut Criterion, name: &str) -> BenchmarkGroup<'a, WallTime> {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+    group.warm_up_time(Duration::from_millis(250));
+    group.measurement_time(Duration::from_millis(250));
+    group
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn id_of_equal_configs() {
+        let config = json!({ "batch_size": 32, "d_model": 1024 });
+
+        let case = BenchCase::identified("b32-d1024", config.clone());
+        let other = BenchCase::identified("b32-d1024", config.clone());
+
+        assert_eq!(case.id, other.id);
+        assert!(case.id.starts_with("b32-d1024-"));
+        assert_eq!(case.id.len(), "b32-d1024-".len() + 8);
+    }
+
+    #[test]
+    fn id_of_configs_with_the_same_label() {
+        let case = BenchCase::identified("b32", json!({ "batch_size": 32, "d_model": 1024 }));
+        let other = BenchCase::identified("b32", json!({ "batch_size": 32, "d_model": 2048 }));
+
+        assert_ne!(case.id, other.id);
+    }
+}
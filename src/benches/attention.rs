@@ -0,0 +1,156 @@
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use burn::{
+    config::Config,
+    module::Module,
+    nn::attention::{
+        generate_autoregressive_mask, generate_padding_mask, MhaInput, MultiHeadAttentionConfig,
+    },
+    tensor::{backend::Backend, Bool, Distribution, Tensor},
+};
+use criterion::Criterion;
+
+pub struct AttentionBenchSuite;
+
+impl BenchSuite for AttentionBenchSuite {
+    type Config = AttentionConfig;
+
+    fn name() -> String {
+        "attention".into()
+    }
+
+    fn description() -> String {
+        "Multi-head attention benchmarks.".into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let label = format!(
+            "b{}-s{}-h{}-d{}",
+            config.batch_size, config.seq_length, config.n_heads, config.d_head
+        );
+
+        match config.mask {
+            AttentionMask::None => label,
+            AttentionMask::Padding => format!("{label}-padding"),
+            AttentionMask::Causal => format!("{label}-causal"),
+        }
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        // The model size is a multiple of the number of heads by construction.
+        match (config.n_heads, config.d_head) {
+            (0, _) => Err("at least one head is expected".into()),
+            (_, 0) => Err("the size of the heads must be positive".into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        vec![
+            AttentionConfig::new(4, 128, 8, 64),
+            AttentionConfig::new(4, 512, 8, 64),
+            AttentionConfig::new(4, 512, 16, 64),
+            AttentionConfig::new(4, 512, 8, 128),
+            AttentionConfig::new(4, 512, 8, 64).with_mask(AttentionMask::Padding),
+            AttentionConfig::new(4, 512, 8, 64).with_mask(AttentionMask::Causal),
+        ]
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(
+            c,
+            &Self::name(),
+            &Self::configs(),
+            &AttentionBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct AttentionConfig {
+    pub batch_size: usize,
+    pub seq_length: usize,
+    pub n_heads: usize,
+    /// Size of each head, the model size being `n_heads * d_head`.
+    pub d_head: usize,
+    #[config(default = "AttentionMask::None")]
+    pub mask: AttentionMask,
+}
+
+/// The mask applied to the attention scores.
+#[derive(Config, Debug, PartialEq)]
+pub enum AttentionMask {
+    None,
+    /// Padding mask, the sequences of the batch going from full to half length.
+    Padding,
+    /// Autoregressive mask, each position only attending to the previous ones.
+    Causal,
+}
+
+pub struct AttentionBench;
+
+impl<B: Backend> Bench<B> for AttentionBench {
+    type Config = AttentionConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let d_model = config.n_heads * config.d_head;
+        let tensor = Tensor::<B, 3>::random(
+            [config.batch_size, config.seq_length, d_model],
+            Distribution::Default,
+        )
+        .to_device(device);
+        let mha = MultiHeadAttentionConfig::new(d_model, config.n_heads)
+            .with_dropout(0.0)
+            .init()
+            .to_device(device);
+
+        let mask_pad = match config.mask {
+            AttentionMask::Padding => Some(padding_mask::<B>(config, device)),
+            _ => None,
+        };
+        let mask_attn = match config.mask {
+            AttentionMask::Causal => Some(generate_autoregressive_mask::<B>(
+                config.batch_size,
+                config.seq_length,
+                device,
+            )),
+            _ => None,
+        };
+
+        Box::new(move || {
+            let mut input = MhaInput::self_attn(tensor.clone());
+
+            if let Some(mask) = &mask_pad {
+                input = input.mask_pad(mask.clone());
+            }
+            if let Some(mask) = &mask_attn {
+                input = input.mask_attn(mask.clone());
+            }
+
+            mha.forward(input).context.flatten(0, 2)
+        })
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let d_model = config.n_heads * config.d_head;
+        let tokens = config.batch_size * config.seq_length;
+        // Query, key, value and output projections.
+        let projections = 4 * 2 * tokens * d_model * d_model;
+        // Attention scores and their weighted sum of the values, the masked scores are still
+        // computed.
+        let attention = 2 * 2 * tokens * config.seq_length * d_model;
+
+        Some(Work::Flops((projections + attention) as u64))
+    }
+}
+
+fn padding_mask<B: Backend>(config: &AttentionConfig, device: &B::Device) -> Tensor<B, 2, Bool> {
+    let tokens = (0..config.batch_size)
+        .map(|i| {
+            let padding = i * config.seq_length / (2 * config.batch_size);
+            vec![1; config.seq_length - padding]
+        })
+        .collect();
+
+    generate_padding_mask::<B>(0, tokens, Some(config.seq_length), device).mask
+}
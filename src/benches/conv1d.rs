@@ -0,0 +1,133 @@
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use crate::conv2d::{check_groups, conv_output_size, same_padding, INVALID_KERNEL};
+use burn::{
+    config::Config,
+    module::Module,
+    nn::conv::Conv1dConfig,
+    nn::PaddingConfig1d,
+    tensor::{backend::Backend, Distribution, Tensor},
+};
+use criterion::Criterion;
+
+pub struct Conv1dBenchSuite;
+
+impl BenchSuite for Conv1dBenchSuite {
+    type Config = Conv1dBenchConfig;
+
+    fn name() -> String {
+        "conv1d".into()
+    }
+
+    fn description() -> String {
+        "Conv1d benchmarks, e.g. on audio and sequences.".into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let conv = &config.conv1d;
+        let mut label = format!(
+            "b{}-{}-c{}x{}-k{}",
+            config.batch_size, config.length, conv.channels_in, conv.channels_out, conv.kernel_size,
+        );
+
+        if conv.stride != 1 {
+            label += &format!("-s{}", conv.stride);
+        }
+        if conv.dilation != 1 {
+            label += &format!("-d{}", conv.dilation);
+        }
+        if conv.groups != 1 {
+            label += &format!("-g{}", conv.groups);
+        }
+
+        label
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        let conv = &config.conv1d;
+        check_groups([conv.channels_in, conv.channels_out], conv.groups)?;
+
+        output_length(conv, config.length)
+            .map(|_| ())
+            .ok_or_else(|| INVALID_KERNEL.into())
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        vec![
+            Conv1dBenchConfig::new(
+                16,
+                1024,
+                Conv1dConfig::new(128, 128, 3).with_padding(PaddingConfig1d::Explicit(1)),
+            ),
+            // Audio front end.
+            Conv1dBenchConfig::new(16, 16000, Conv1dConfig::new(1, 64, 10).with_stride(5)),
+            // Dilated convolution, with the kernel of the WaveNet layers.
+            Conv1dBenchConfig::new(16, 4096, Conv1dConfig::new(64, 64, 2).with_dilation(8)),
+            // Depthwise convolution, as in the Conformer convolution module.
+            Conv1dBenchConfig::new(
+                16,
+                512,
+                Conv1dConfig::new(256, 256, 31)
+                    .with_groups(256)
+                    .with_padding(PaddingConfig1d::Explicit(15)),
+            ),
+            Conv1dBenchConfig::new(16, 512, Conv1dConfig::new(256, 1024, 1)),
+        ]
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(c, &Self::name(), &Self::configs(), &Conv1dBench, device);
+    }
+}
+
+#[derive(Config)]
+pub struct Conv1dBenchConfig {
+    pub batch_size: usize,
+    pub length: usize,
+    pub conv1d: Conv1dConfig,
+}
+
+pub struct Conv1dBench;
+
+impl<B: Backend> Bench<B> for Conv1dBench {
+    type Config = Conv1dBenchConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let tensor = Tensor::<B, 3>::random(
+            [config.batch_size, config.conv1d.channels_in, config.length],
+            Distribution::Uniform(0.0, 1.0),
+        )
+        .to_device(device);
+        let conv = config.conv1d.init().to_device(device);
+
+        Box::new(move || conv.forward(tensor.clone()).flatten(0, 2))
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let conv = &config.conv1d;
+        let flops = 2
+            * config.batch_size
+            * conv.channels_out
+            * output_length(conv, config.length)?
+            * (conv.channels_in / conv.groups)
+            * conv.kernel_size;
+
+        Some(Work::Flops(flops as u64))
+    }
+}
+
+/// The length of the output of the convolution, none when it has no output.
+fn output_length(conv: &Conv1dConfig, length: usize) -> Option<usize> {
+    let padding = match conv.padding {
+        PaddingConfig1d::Same => same_padding(length, conv.kernel_size, conv.stride)?,
+        PaddingConfig1d::Valid => 0,
+        PaddingConfig1d::Explicit(padding) => padding,
+    };
+
+    conv_output_size(
+        length,
+        padding,
+        conv.kernel_size,
+        conv.stride,
+        conv.dilation,
+    )
+}
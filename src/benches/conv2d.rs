@@ -1,8 +1,4 @@
-use crate::{
-    bench::{run_benchmark, Bench, BenchFunc, BenchSuite},
-    device, BenchBackend,
-};
-use burn::backend::Autodiff;
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
 use burn::{
     config::Config,
     module::Module,
@@ -15,27 +11,139 @@ use criterion::Criterion;
 pub struct Conv2dBenchSuite;
 
 impl BenchSuite for Conv2dBenchSuite {
+    type Config = Conv2dBenchConfig;
+
     fn name() -> String {
         "Conv2d".into()
     }
 
-    fn details() -> String {
-        let mut details = String::from("Conv2d benchmarks.\n\n");
+    fn description() -> String {
+        "Conv2d benchmarks, with regular, strided, dilated, grouped and depthwise convolutions."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let conv = &config.conv2d;
+        let mut label = format!(
+            "b{}-{}x{}-l{}-c{}x{}-k{}x{}",
+            config.batch_size,
+            config.height,
+            config.width,
+            config.num_layers,
+            conv.channels[0],
+            conv.channels[1],
+            conv.kernel_size[0],
+            conv.kernel_size[1],
+        );
+
+        if conv.stride != [1, 1] {
+            label += &format!("-s{}x{}", conv.stride[0], conv.stride[1]);
+        }
+        if conv.dilation != [1, 1] {
+            label += &format!("-d{}x{}", conv.dilation[0], conv.dilation[1]);
+        }
+        if conv.groups != 1 {
+            label += &format!("-g{}", conv.groups);
+        }
+
+        label
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        let [channels_in, channels_out] = config.conv2d.channels;
 
-        configs()
-            .into_iter()
-            .enumerate()
-            .for_each(|(i, config)| details += format!("- {} => `{}`\n", i + 1, config).as_str());
+        if config.num_layers > 1 && channels_in != channels_out {
+            return Err("stacked convolutions must keep the number of channels".into());
+        }
+        check_groups(config.conv2d.channels, config.conv2d.groups)?;
 
-        details
+        (0..config.num_layers)
+            .try_fold((config.height, config.width), |(height, width), _| {
+                output_size(&config.conv2d, height, width)
+            })
+            .map(|_| ())
+            .ok_or_else(|| INVALID_KERNEL.into())
     }
 
-    fn run(c: &mut Criterion) {
-        let name = Self::name();
-        let name_autodiff = format!("{}-autodiff", Self::name());
+    fn default_configs() -> Vec<Self::Config> {
+        vec![
+            Conv2dBenchConfig::new(
+                4,
+                32,
+                32,
+                2,
+                Conv2dConfig::new([1, 1], [3, 3]).with_padding(PaddingConfig2d::Same),
+            ),
+            Conv2dBenchConfig::new(
+                4,
+                64,
+                64,
+                2,
+                Conv2dConfig::new([1, 1], [3, 3]).with_padding(PaddingConfig2d::Same),
+            ),
+            // ResNet stem.
+            Conv2dBenchConfig::new(
+                8,
+                224,
+                224,
+                1,
+                Conv2dConfig::new([3, 64], [7, 7])
+                    .with_stride([2, 2])
+                    .with_padding(PaddingConfig2d::Explicit(3, 3)),
+            ),
+            Conv2dBenchConfig::new(
+                8,
+                56,
+                56,
+                2,
+                Conv2dConfig::new([64, 64], [3, 3]).with_padding(PaddingConfig2d::Same),
+            ),
+            // Downsampling.
+            Conv2dBenchConfig::new(
+                8,
+                28,
+                28,
+                1,
+                Conv2dConfig::new([128, 256], [3, 3])
+                    .with_stride([2, 2])
+                    .with_padding(PaddingConfig2d::Explicit(1, 1)),
+            ),
+            // Bottleneck projection.
+            Conv2dBenchConfig::new(8, 56, 56, 1, Conv2dConfig::new([256, 64], [1, 1])),
+            Conv2dBenchConfig::new(
+                8,
+                56,
+                56,
+                1,
+                Conv2dConfig::new([64, 64], [3, 3])
+                    .with_dilation([2, 2])
+                    .with_padding(PaddingConfig2d::Explicit(2, 2)),
+            ),
+            // ResNeXt grouped convolution.
+            Conv2dBenchConfig::new(
+                8,
+                28,
+                28,
+                1,
+                Conv2dConfig::new([256, 256], [3, 3])
+                    .with_groups(32)
+                    .with_padding(PaddingConfig2d::Explicit(1, 1)),
+            ),
+            // MobileNet depthwise convolution.
+            Conv2dBenchConfig::new(
+                8,
+                28,
+                28,
+                1,
+                Conv2dConfig::new([256, 256], [3, 3])
+                    .with_groups(256)
+                    .with_padding(PaddingConfig2d::Explicit(1, 1)),
+            ),
+        ]
+    }
 
-        run_benchmark(c, &name, configs(), Conv2dBench::new());
-        run_benchmark(c, &name_autodiff, configs(), Conv2dBenchAD::new());
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(c, &Self::name(), &Self::configs(), &Conv2dBench, device);
     }
 }
 
@@ -73,36 +181,16 @@ impl<B: Backend> Conv2dBlock<B> {
     }
 }
 
-fn configs() -> Vec<Conv2dBenchConfig> {
-    vec![
-        Conv2dBenchConfig::new(
-            4,
-            32,
-            32,
-            2,
-            Conv2dConfig::new([1, 1], [3, 3]).with_padding(PaddingConfig2d::Same),
-        ),
-        Conv2dBenchConfig::new(
-            4,
-            64,
-            64,
-            2,
-            Conv2dConfig::new([1, 1], [3, 3]).with_padding(PaddingConfig2d::Same),
-        ),
-    ]
-}
+/// Number of passes of the block executed at each iteration.
+const PASSES: usize = 10;
 
-#[derive(new)]
 pub struct Conv2dBench;
-#[derive(new)]
-pub struct Conv2dBenchAD;
 
-impl Bench for Conv2dBench {
+impl<B: Backend> Bench<B> for Conv2dBench {
     type Config = Conv2dBenchConfig;
 
-    fn prepare(&self, config: &Self::Config) -> BenchFunc {
-        let device = device();
-        let tensor = Tensor::<BenchBackend, 4>::random(
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let tensor = Tensor::<B, 4>::random(
             [
                 config.batch_size,
                 config.conv2d.channels[0],
@@ -111,46 +199,94 @@ impl Bench for Conv2dBench {
             ],
             Distribution::Uniform(0.0, 1.0),
         )
-        .to_device(&device);
-        let module = Conv2dBlock::new(config).to_device(&device);
+        .to_device(device);
+        let module = Conv2dBlock::new(config).to_device(device);
 
-        Box::new(move || {
-            let mut output = None;
+        Box::new(move || module.forward(tensor.clone()).flatten(0, 3))
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let conv = &config.conv2d;
+        let [channels_in, channels_out] = conv.channels;
+        let [kernel_height, kernel_width] = conv.kernel_size;
+        let (mut height, mut width) = (config.height, config.width);
+        let mut flops = 0;
 
-            for _ in 0..10 {
-                output = Some(module.forward(tensor.clone()));
-            }
+        for _ in 0..config.num_layers {
+            (height, width) = output_size(conv, height, width)?;
+            flops += 2
+                * config.batch_size
+                * channels_out
+                * height
+                * width
+                * (channels_in / conv.groups)
+                * kernel_height
+                * kernel_width;
+        }
+
+        Some(Work::Flops((PASSES * flops) as u64))
+    }
 
-            <BenchBackend as Backend>::sync(&device);
-        })
+    fn passes(&self, _config: &Self::Config) -> usize {
+        PASSES
     }
 }
 
-impl Bench for Conv2dBenchAD {
-    type Config = Conv2dBenchConfig;
+/// Reason of the configs whose convolutions have no output.
+pub(crate) const INVALID_KERNEL: &str =
+    "the kernel must fit in the padded input and the stride must be positive";
 
-    fn prepare(&self, config: &Self::Config) -> BenchFunc {
-        type ADBackend = Autodiff<BenchBackend>;
+/// Check that the groups divide the input and output channels.
+pub(crate) fn check_groups(channels: [usize; 2], groups: usize) -> Result<(), String> {
+    let [channels_in, channels_out] = channels;
 
-        let device = device();
-        let tensor = Tensor::<ADBackend, 4>::random(
-            [
-                config.batch_size,
-                config.conv2d.channels[0],
-                config.height,
-                config.width,
-            ],
-            Distribution::Uniform(0.0, 1.0),
-        )
-        .to_device(&device);
-        let module = Conv2dBlock::new(config).to_device(&device);
-
-        Box::new(move || {
-            for _ in 0..10 {
-                let tensor = module.forward(tensor.clone());
-                let _grads = tensor.backward();
-                <BenchBackend as Backend>::sync(&device);
-            }
-        })
+    match groups {
+        0 => Err("at least one group is expected".into()),
+        _ if channels_in % groups != 0 || channels_out % groups != 0 => Err(format!(
+            "the {channels_in} input and {channels_out} output channels must be multiples of \
+             the {groups} groups"
+        )),
+        _ => Ok(()),
     }
 }
+
+/// The spatial size of the output of the convolution, none when it has no output.
+fn output_size(conv: &Conv2dConfig, height: usize, width: usize) -> Option<(usize, usize)> {
+    let size = |size: usize, i: usize| {
+        let (kernel_size, stride) = (conv.kernel_size[i], conv.stride[i]);
+        let padding = match conv.padding {
+            PaddingConfig2d::Same => same_padding(size, kernel_size, stride)?,
+            PaddingConfig2d::Valid => 0,
+            PaddingConfig2d::Explicit(height, width) => [height, width][i],
+        };
+
+        conv_output_size(size, padding, kernel_size, stride, conv.dilation[i])
+    };
+
+    Some((size(height, 0)?, size(width, 1)?))
+}
+
+/// The padding of each side computed by burn for the same padding, so the output keeps the size
+/// of the input whatever the stride, the dilation being ignored.
+pub(crate) fn same_padding(size: usize, kernel_size: usize, stride: usize) -> Option<usize> {
+    let padded = size.checked_sub(1)? * stride + kernel_size;
+
+    Some(padded.saturating_sub(size).div_ceil(2))
+}
+
+/// The size of the output of a convolution along one dimension, none when the dilated kernel
+/// doesn't fit in the padded input or when the stride is zero.
+pub(crate) fn conv_output_size(
+    size: usize,
+    padding: usize,
+    kernel_size: usize,
+    stride: usize,
+    dilation: usize,
+) -> Option<usize> {
+    let kernel_span = dilation * kernel_size.checked_sub(1)? + 1;
+    let steps = (size + 2 * padding)
+        .checked_sub(kernel_span)?
+        .checked_div(stride)?;
+
+    Some(steps + 1)
+}
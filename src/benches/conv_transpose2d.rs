@@ -0,0 +1,160 @@
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use crate::conv2d::{check_groups, conv_output_size};
+use burn::{
+    config::Config,
+    module::Module,
+    nn::conv::ConvTranspose2dConfig,
+    tensor::{backend::Backend, Distribution, Tensor},
+};
+use criterion::Criterion;
+
+pub struct ConvTranspose2dBenchSuite;
+
+impl BenchSuite for ConvTranspose2dBenchSuite {
+    type Config = ConvTranspose2dBenchConfig;
+
+    fn name() -> String {
+        "conv_transpose2d".into()
+    }
+
+    fn description() -> String {
+        "ConvTranspose2d benchmarks, as used to upsample in decoders and generators.".into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let conv = &config.conv_transpose2d;
+        let mut label = format!(
+            "b{}-{}x{}-c{}x{}-k{}x{}-s{}x{}",
+            config.batch_size,
+            config.height,
+            config.width,
+            conv.channels[0],
+            conv.channels[1],
+            conv.kernel_size[0],
+            conv.kernel_size[1],
+            conv.stride[0],
+            conv.stride[1],
+        );
+
+        if conv.groups != 1 {
+            label += &format!("-g{}", conv.groups);
+        }
+
+        label
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        let conv = &config.conv_transpose2d;
+        check_groups(conv.channels, conv.groups)?;
+
+        [(config.height, 0), (config.width, 1)]
+            .into_iter()
+            .try_for_each(|(size, i)| output_size(conv, size, i).map(|_| ()))
+            .ok_or_else(|| {
+                "the output must not be empty, the stride must be positive and larger than the \
+                 output padding"
+                    .into()
+            })
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        vec![
+            // U-Net upsampling.
+            ConvTranspose2dBenchConfig::new(
+                8,
+                32,
+                32,
+                ConvTranspose2dConfig::new([256, 128], [2, 2]).with_stride([2, 2]),
+            ),
+            // DCGAN generator.
+            ConvTranspose2dBenchConfig::new(
+                64,
+                16,
+                16,
+                ConvTranspose2dConfig::new([128, 64], [4, 4])
+                    .with_stride([2, 2])
+                    .with_padding([1, 1]),
+            ),
+            ConvTranspose2dBenchConfig::new(
+                8,
+                56,
+                56,
+                ConvTranspose2dConfig::new([64, 64], [3, 3]).with_padding([1, 1]),
+            ),
+        ]
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(
+            c,
+            &Self::name(),
+            &Self::configs(),
+            &ConvTranspose2dBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct ConvTranspose2dBenchConfig {
+    pub batch_size: usize,
+    pub height: usize,
+    pub width: usize,
+    pub conv_transpose2d: ConvTranspose2dConfig,
+}
+
+pub struct ConvTranspose2dBench;
+
+impl<B: Backend> Bench<B> for ConvTranspose2dBench {
+    type Config = ConvTranspose2dBenchConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let tensor = Tensor::<B, 4>::random(
+            [
+                config.batch_size,
+                config.conv_transpose2d.channels[0],
+                config.height,
+                config.width,
+            ],
+            Distribution::Uniform(0.0, 1.0),
+        )
+        .to_device(device);
+        let conv = config.conv_transpose2d.init().to_device(device);
+
+        Box::new(move || conv.forward(tensor.clone()).flatten(0, 3))
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let conv = &config.conv_transpose2d;
+        let [channels_in, channels_out] = conv.channels;
+        let [kernel_height, kernel_width] = conv.kernel_size;
+        // Each input element is multiplied with a kernel for each output channel of its group.
+        let flops = 2
+            * config.batch_size
+            * channels_in
+            * config.height
+            * config.width
+            * (channels_out / conv.groups)
+            * kernel_height
+            * kernel_width;
+
+        Some(Work::Flops(flops as u64))
+    }
+}
+
+/// The size of the output of the transposed convolution along the dimension, none when it has no
+/// output or when the convolution with the same parameters doesn't map it back to the input.
+fn output_size(conv: &ConvTranspose2dConfig, size: usize, i: usize) -> Option<usize> {
+    let (kernel_size, stride) = (conv.kernel_size[i], conv.stride[i]);
+    let (padding, dilation) = (conv.padding[i], conv.dilation[i]);
+    let output = (size.checked_sub(1)? * stride
+        + dilation * kernel_size.checked_sub(1)?
+        + conv.padding_out[i]
+        + 1)
+    .checked_sub(2 * padding)?;
+
+    match conv_output_size(output, padding, kernel_size, stride, dilation) {
+        Some(input) if input == size => Some(output),
+        _ => None,
+    }
+}
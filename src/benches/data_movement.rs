@@ -0,0 +1,258 @@
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use burn::{
+    config::Config,
+    tensor::{backend::Backend, Distribution, Int, Tensor},
+};
+use criterion::{black_box, Criterion};
+
+pub struct DataMovementBenchSuite;
+
+impl BenchSuite for DataMovementBenchSuite {
+    type Config = DataMovementConfig;
+
+    fn name() -> String {
+        "data_movement".into()
+    }
+
+    fn description() -> String {
+        "Tensor layout and data movement benchmarks, on contiguous and transposed inputs. The \
+         bandwidth is computed from the bytes read and written by a copy, so views are expected \
+         to show a high bandwidth. The move to the device the tensor is already on measures the \
+         overhead of a transfer. The transfers have no gradient, so they have no autodiff \
+         variant."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let label = format!("{}-{}x{}", config.op.name(), config.rows, config.cols);
+
+        match config.contiguous {
+            true => label,
+            false => format!("{label}-t"),
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        let ops = [
+            DataMovementOp::Reshape,
+            DataMovementOp::Transpose,
+            DataMovementOp::Slice,
+            DataMovementOp::SliceAssign,
+            DataMovementOp::Cat,
+            DataMovementOp::Gather,
+            DataMovementOp::Scatter,
+            DataMovementOp::Select,
+            DataMovementOp::ToDevice,
+            DataMovementOp::IntoData,
+            DataMovementOp::FromData,
+        ];
+
+        ops.into_iter()
+            .flat_map(|op| {
+                [true, false].map(|contiguous| {
+                    DataMovementConfig::new(op.clone(), 1024, 1024).with_contiguous(contiguous)
+                })
+            })
+            .collect()
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(
+            c,
+            &Self::name(),
+            &Self::configs(),
+            &DataMovementBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct DataMovementConfig {
+    pub op: DataMovementOp,
+    pub rows: usize,
+    pub cols: usize,
+    /// Whether the input is contiguous, otherwise it is a transposed view.
+    #[config(default = true)]
+    pub contiguous: bool,
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum DataMovementOp {
+    /// Reshape to `[cols, rows]`.
+    Reshape,
+    /// Swap the two dimensions.
+    Transpose,
+    /// Slice the top left quarter.
+    Slice,
+    /// Assign the top left quarter.
+    SliceAssign,
+    /// Concatenate with another tensor along the rows.
+    Cat,
+    /// Gather random columns of each row.
+    Gather,
+    /// Scatter to random columns of each row.
+    Scatter,
+    /// Select half of the rows at random.
+    Select,
+    /// Move the tensor to the device it already is on.
+    ToDevice,
+    /// Read the tensor from the device.
+    IntoData,
+    /// Write the tensor to the device, laid out as `[cols, rows]` and transposed when the input
+    /// isn't contiguous.
+    FromData,
+}
+
+impl DataMovementOp {
+    fn name(&self) -> &'static str {
+        match self {
+            DataMovementOp::Reshape => "reshape",
+            DataMovementOp::Transpose => "transpose",
+            DataMovementOp::Slice => "slice",
+            DataMovementOp::SliceAssign => "slice_assign",
+            DataMovementOp::Cat => "cat",
+            DataMovementOp::Gather => "gather",
+            DataMovementOp::Scatter => "scatter",
+            DataMovementOp::Select => "select",
+            DataMovementOp::ToDevice => "to_device",
+            DataMovementOp::IntoData => "into_data",
+            DataMovementOp::FromData => "from_data",
+        }
+    }
+}
+
+pub struct DataMovementBench;
+
+impl<B: Backend> Bench<B> for DataMovementBench {
+    type Config = DataMovementConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let (rows, cols) = (config.rows, config.cols);
+        let random = |shape: [usize; 2]| {
+            Tensor::<B, 2>::random(shape, Distribution::Default)
+                .to_device(device)
+                .require_grad()
+        };
+        // The sampled floats can be rounded up to `max`, so the indices are clamped to stay in
+        // bounds.
+        let indices = |shape: [usize; 2], max: usize| -> Tensor<B, 2, Int> {
+            Tensor::<B, 2>::random(shape, Distribution::Uniform(0.0, max as f64))
+                .to_device(device)
+                .int()
+                .clamp(0, max as i64 - 1)
+        };
+
+        let contiguous = config.contiguous;
+        let tensor = match contiguous {
+            true => random([rows, cols]),
+            false => random([cols, rows]).transpose(),
+        };
+        let device = device.clone();
+
+        match config.op {
+            DataMovementOp::Reshape => {
+                Box::new(move || tensor.clone().reshape([cols, rows]).flatten(0, 1))
+            }
+            DataMovementOp::Transpose => Box::new(move || tensor.clone().transpose().flatten(0, 1)),
+            DataMovementOp::Slice => Box::new(move || {
+                tensor
+                    .clone()
+                    .slice([0..rows / 2, 0..cols / 2])
+                    .flatten(0, 1)
+            }),
+            DataMovementOp::SliceAssign => {
+                let values = random([rows / 2, cols / 2]);
+
+                Box::new(move || {
+                    tensor
+                        .clone()
+                        .slice_assign([0..rows / 2, 0..cols / 2], values.clone())
+                        .flatten(0, 1)
+                })
+            }
+            DataMovementOp::Cat => {
+                let other = random([rows, cols]);
+
+                Box::new(move || Tensor::cat(vec![tensor.clone(), other.clone()], 0).flatten(0, 1))
+            }
+            DataMovementOp::Gather => {
+                let indices = indices([rows, cols], cols);
+
+                Box::new(move || tensor.clone().gather(1, indices.clone()).flatten(0, 1))
+            }
+            DataMovementOp::Scatter => {
+                let indices = indices([rows, cols], cols);
+                let values = random([rows, cols]);
+
+                Box::new(move || {
+                    tensor
+                        .clone()
+                        .scatter(1, indices.clone(), values.clone())
+                        .flatten(0, 1)
+                })
+            }
+            DataMovementOp::Select => {
+                let indices = indices([1, rows / 2], rows).flatten::<1>(0, 1);
+
+                Box::new(move || tensor.clone().select(0, indices.clone()).flatten(0, 1))
+            }
+            DataMovementOp::ToDevice => {
+                Box::new(move || tensor.clone().to_device(&device).flatten(0, 1))
+            }
+            DataMovementOp::IntoData => {
+                let output = Tensor::<B, 1>::zeros([1]).to_device(&device);
+
+                Box::new(move || {
+                    black_box(tensor.clone().into_data());
+                    output.clone()
+                })
+            }
+            DataMovementOp::FromData => {
+                let data = match contiguous {
+                    true => tensor.into_data(),
+                    false => tensor.transpose().into_data(),
+                };
+
+                Box::new(move || {
+                    let tensor = Tensor::<B, 2>::from_data(data.clone()).to_device(&device);
+
+                    match contiguous {
+                        true => tensor.flatten(0, 1),
+                        false => tensor.transpose().flatten(0, 1),
+                    }
+                })
+            }
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let elements = config.rows * config.cols;
+        let float = std::mem::size_of::<B::FloatElem>();
+        let int = std::mem::size_of::<B::IntElem>();
+
+        // Bytes read and written, the tensors being cloned before being modified.
+        let bytes = match config.op {
+            DataMovementOp::Reshape
+            | DataMovementOp::Transpose
+            | DataMovementOp::ToDevice
+            | DataMovementOp::IntoData
+            | DataMovementOp::FromData => 2 * elements * float,
+            DataMovementOp::Slice => 2 * elements / 4 * float,
+            DataMovementOp::SliceAssign => (2 * elements + 2 * elements / 4) * float,
+            DataMovementOp::Cat => 4 * elements * float,
+            DataMovementOp::Gather => elements * (2 * float + int),
+            DataMovementOp::Scatter => elements * (4 * float + int),
+            DataMovementOp::Select => elements / 2 * 2 * float + config.rows / 2 * int,
+        };
+
+        Some(Work::Bytes(bytes as u64))
+    }
+
+    fn differentiable(&self, config: &Self::Config) -> bool {
+        !matches!(
+            config.op,
+            DataMovementOp::ToDevice | DataMovementOp::IntoData | DataMovementOp::FromData
+        )
+    }
+}
@@ -0,0 +1,228 @@
+use crate::bench::{
+    run_benchmark, run_benchmark_batched, BatchedBench, Bench, BenchFunc, BenchSuite, RoutineFunc,
+    SetupFunc, Work,
+};
+use burn::{
+    config::Config,
+    module::Module,
+    nn::attention::generate_autoregressive_mask,
+    nn::transformer::{
+        TransformerDecoder, TransformerDecoderAutoregressiveCache, TransformerDecoderConfig,
+        TransformerDecoderInput,
+    },
+    tensor::{backend::Backend, Bool, Distribution, Tensor},
+};
+use criterion::Criterion;
+use std::rc::Rc;
+
+pub struct DecoderBenchSuite;
+
+impl BenchSuite for DecoderBenchSuite {
+    type Config = DecoderConfig;
+
+    fn name() -> String {
+        "decoder".into()
+    }
+
+    fn description() -> String {
+        "Autoregressive transformer decoder inference benchmarks, the prefill phase processing \
+         the prompt and the decode phase generating the tokens one by one with the cache."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        format!(
+            "b{}-p{}-g{}-d{}-l{}",
+            config.batch_size,
+            config.prompt_length,
+            config.decode_steps,
+            config.decoder.d_model,
+            config.decoder.n_layers,
+        )
+    }
+
+    fn element() -> Option<&'static str> {
+        Some("token")
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        let (d_model, n_heads) = (config.decoder.d_model, config.decoder.n_heads);
+
+        match n_heads {
+            0 => Err("at least one head is expected".into()),
+            _ if d_model % n_heads != 0 => Err(format!(
+                "the model size {d_model} must be a multiple of the {n_heads} heads"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        let decoder = |d_model, n_heads, n_layers| {
+            TransformerDecoderConfig::new(d_model, 4 * d_model, n_heads, n_layers).with_dropout(0.0)
+        };
+
+        vec![
+            DecoderConfig::new(1, 128, 32, 16, decoder(256, 4, 4)),
+            DecoderConfig::new(1, 128, 32, 16, decoder(512, 8, 6)),
+            DecoderConfig::new(1, 128, 32, 16, decoder(1024, 16, 8)),
+            DecoderConfig::new(8, 128, 32, 16, decoder(512, 8, 6)),
+        ]
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        let cases = Self::configs();
+
+        run_benchmark::<B, _, _>(
+            c,
+            &format!("{}-prefill", Self::name()),
+            &cases,
+            &PrefillBench,
+            device,
+        );
+        run_benchmark_batched::<B, _, _>(
+            c,
+            &format!("{}-decode", Self::name()),
+            &cases,
+            &DecodeBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct DecoderConfig {
+    pub batch_size: usize,
+    /// Number of tokens processed by the prefill phase.
+    pub prompt_length: usize,
+    /// Number of tokens generated by the decode phase, after the prompt.
+    pub decode_steps: usize,
+    /// Number of tokens of the encoder output attended by the decoder.
+    pub memory_length: usize,
+    pub decoder: TransformerDecoderConfig,
+}
+
+/// The inputs shared by the prefill and the decode phases.
+struct Inputs<B: Backend> {
+    decoder: TransformerDecoder<B>,
+    prompt: Tensor<B, 3>,
+    memory: Tensor<B, 3>,
+}
+
+impl<B: Backend> Inputs<B> {
+    fn new(config: &DecoderConfig, device: &B::Device) -> Self {
+        let random = |length| {
+            Tensor::<B, 3>::random(
+                [config.batch_size, length, config.decoder.d_model],
+                Distribution::Default,
+            )
+            .to_device(device)
+        };
+
+        Self {
+            decoder: config.decoder.init().to_device(device),
+            prompt: random(config.prompt_length),
+            memory: random(config.memory_length),
+        }
+    }
+
+    /// Run the decoder on the target, only computing the positions missing from the cache.
+    fn forward(
+        &self,
+        target: Tensor<B, 3>,
+        mask: Tensor<B, 3, Bool>,
+        cache: &mut TransformerDecoderAutoregressiveCache<B>,
+    ) -> Tensor<B, 3> {
+        let input =
+            TransformerDecoderInput::new(target, self.memory.clone()).target_mask_attn(mask);
+
+        self.decoder.forward_autoregressive_inference(input, cache)
+    }
+}
+
+/// Process the prompt with an empty cache.
+pub struct PrefillBench;
+
+impl<B: Backend> Bench<B> for PrefillBench {
+    type Config = DecoderConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let inputs = Inputs::<B>::new(config, device);
+        let mask =
+            generate_autoregressive_mask::<B>(config.batch_size, config.prompt_length, device);
+
+        Box::new(move || {
+            let mut cache = inputs.decoder.new_autoregressive_cache();
+
+            inputs
+                .forward(inputs.prompt.clone(), mask.clone(), &mut cache)
+                .flatten(0, 2)
+        })
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        Some(Work::Elements(
+            (config.batch_size * config.prompt_length) as u64,
+        ))
+    }
+}
+
+/// Generate the tokens one by one, starting from the cache filled by the prefill phase.
+///
+/// The generated tokens are random, so only the decoder is measured.
+pub struct DecodeBench;
+
+impl<B: Backend> BatchedBench<B> for DecodeBench {
+    type Config = DecoderConfig;
+    type State = (TransformerDecoderAutoregressiveCache<B>, Tensor<B, 3>);
+
+    fn prepare(
+        &self,
+        config: &Self::Config,
+        device: &B::Device,
+    ) -> (SetupFunc<Self::State>, RoutineFunc<B, Self::State>) {
+        let inputs = Rc::new(Inputs::<B>::new(config, device));
+        let masks: Vec<_> = (config.prompt_length..=config.prompt_length + config.decode_steps)
+            .map(|length| generate_autoregressive_mask::<B>(config.batch_size, length, device))
+            .collect();
+        let tokens: Vec<_> = (0..config.decode_steps)
+            .map(|_| {
+                Tensor::<B, 3>::random(
+                    [config.batch_size, 1, config.decoder.d_model],
+                    Distribution::Default,
+                )
+                .to_device(device)
+            })
+            .collect();
+
+        let setup = {
+            let inputs = inputs.clone();
+            let mask = masks[0].clone();
+
+            move || {
+                let mut cache = inputs.decoder.new_autoregressive_cache();
+                inputs.forward(inputs.prompt.clone(), mask.clone(), &mut cache);
+
+                (cache, inputs.prompt.clone())
+            }
+        };
+        let routine = move |(mut cache, mut target): Self::State| {
+            let mut output = None;
+
+            for (token, mask) in tokens.iter().zip(masks[1..].iter()) {
+                target = Tensor::cat(vec![target, token.clone()], 1);
+                output = Some(inputs.forward(target.clone(), mask.clone(), &mut cache));
+            }
+
+            output.unwrap_or(target).flatten(0, 2)
+        };
+
+        (Box::new(setup), Box::new(routine))
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        Some(Work::Elements(
+            (config.batch_size * config.decode_steps) as u64,
+        ))
+    }
+}
@@ -0,0 +1,205 @@
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use burn::{
+    config::Config,
+    tensor::{activation, backend::Backend, Distribution, Tensor},
+};
+use criterion::Criterion;
+
+pub struct ElementwiseBenchSuite;
+
+impl BenchSuite for ElementwiseBenchSuite {
+    type Config = ElementwiseConfig;
+
+    fn name() -> String {
+        "elementwise".into()
+    }
+
+    fn description() -> String {
+        "Elementwise operations and activations benchmarks, executed alone or chained. The \
+         bandwidth is computed as if each operation was executed by its own kernel, so a fused \
+         chain shows a higher bandwidth than its operations."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let ops: Vec<_> = config.ops.iter().map(ElementwiseOp::name).collect();
+        let shape: Vec<_> = config.shape.iter().map(usize::to_string).collect();
+
+        format!("{}-{}", ops.join("_"), shape.join("x"))
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        match config.shape.len() {
+            1..=4 => Ok(()),
+            _ => Err("tensors with 1 to 4 dimensions are supported".into()),
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        let ops = [
+            ElementwiseOp::Add,
+            ElementwiseOp::Mul,
+            ElementwiseOp::Exp,
+            ElementwiseOp::Log,
+            ElementwiseOp::Tanh,
+            ElementwiseOp::Sigmoid,
+            ElementwiseOp::Relu,
+            ElementwiseOp::Gelu,
+            ElementwiseOp::Powf(2.5),
+            ElementwiseOp::Clamp(0.75, 1.25),
+        ];
+        let mut configs: Vec<_> = ops
+            .into_iter()
+            .map(|op| ElementwiseConfig::new(vec![op], vec![1024, 1024]))
+            .collect();
+
+        // Chains, e.g. a bias and an activation.
+        configs.push(ElementwiseConfig::new(
+            vec![ElementwiseOp::Mul, ElementwiseOp::Add, ElementwiseOp::Gelu],
+            vec![1024, 1024],
+        ));
+        configs.push(ElementwiseConfig::new(
+            vec![
+                ElementwiseOp::Exp,
+                ElementwiseOp::Log,
+                ElementwiseOp::Tanh,
+                ElementwiseOp::Sigmoid,
+                ElementwiseOp::Relu,
+            ],
+            vec![1024, 1024],
+        ));
+        // Tiny tensors, dominated by the launch overhead.
+        configs.push(ElementwiseConfig::new(vec![ElementwiseOp::Add], vec![16]));
+        configs.push(ElementwiseConfig::new(
+            vec![ElementwiseOp::Add; 10],
+            vec![16],
+        ));
+
+        configs
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(
+            c,
+            &Self::name(),
+            &Self::configs(),
+            &ElementwiseBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct ElementwiseConfig {
+    /// Operations applied in order, the binary ones taking a second tensor of the same shape.
+    pub ops: Vec<ElementwiseOp>,
+    /// Shape of the tensors, with 1 to 4 dimensions.
+    pub shape: Vec<usize>,
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum ElementwiseOp {
+    Add,
+    Mul,
+    Exp,
+    Log,
+    Tanh,
+    Sigmoid,
+    Relu,
+    Gelu,
+    /// Power with the given exponent.
+    Powf(f32),
+    /// Clamp between the given min and max.
+    Clamp(f32, f32),
+}
+
+impl ElementwiseOp {
+    fn name(&self) -> &'static str {
+        match self {
+            ElementwiseOp::Add => "add",
+            ElementwiseOp::Mul => "mul",
+            ElementwiseOp::Exp => "exp",
+            ElementwiseOp::Log => "log",
+            ElementwiseOp::Tanh => "tanh",
+            ElementwiseOp::Sigmoid => "sigmoid",
+            ElementwiseOp::Relu => "relu",
+            ElementwiseOp::Gelu => "gelu",
+            ElementwiseOp::Powf(_) => "powf",
+            ElementwiseOp::Clamp(..) => "clamp",
+        }
+    }
+
+    /// Number of tensors read by the operation.
+    fn inputs(&self) -> usize {
+        match self {
+            ElementwiseOp::Add | ElementwiseOp::Mul => 2,
+            _ => 1,
+        }
+    }
+
+    fn apply<B: Backend, const D: usize>(
+        &self,
+        tensor: Tensor<B, D>,
+        other: &Tensor<B, D>,
+    ) -> Tensor<B, D> {
+        match self {
+            ElementwiseOp::Add => tensor + other.clone(),
+            ElementwiseOp::Mul => tensor * other.clone(),
+            ElementwiseOp::Exp => tensor.exp(),
+            ElementwiseOp::Log => tensor.log(),
+            ElementwiseOp::Tanh => tensor.tanh(),
+            ElementwiseOp::Sigmoid => activation::sigmoid(tensor),
+            ElementwiseOp::Relu => activation::relu(tensor),
+            ElementwiseOp::Gelu => activation::gelu(tensor),
+            ElementwiseOp::Powf(exponent) => tensor.powf(*exponent),
+            ElementwiseOp::Clamp(min, max) => tensor.clamp(*min, *max),
+        }
+    }
+}
+
+pub struct ElementwiseBench;
+
+impl<B: Backend> Bench<B> for ElementwiseBench {
+    type Config = ElementwiseConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        match config.shape.len() {
+            1 => prepare::<B, 1>(config, device),
+            2 => prepare::<B, 2>(config, device),
+            3 => prepare::<B, 3>(config, device),
+            4 => prepare::<B, 4>(config, device),
+            _ => unreachable!("The rank is validated by the suite"),
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let elements: usize = config.shape.iter().product();
+        // Each operation reads its inputs and writes its output.
+        let accesses: usize = config.ops.iter().map(|op| op.inputs() + 1).sum();
+        let bytes = accesses * elements * std::mem::size_of::<B::FloatElem>();
+
+        Some(Work::Bytes(bytes as u64))
+    }
+}
+
+fn prepare<B: Backend, const D: usize>(
+    config: &ElementwiseConfig,
+    device: &B::Device,
+) -> BenchFunc<B> {
+    let mut shape = [0; D];
+    shape.copy_from_slice(&config.shape);
+    // Positive values, so the logarithm and the power are defined.
+    let random = || {
+        Tensor::<B, D>::random(shape, Distribution::Uniform(0.5, 1.5))
+            .to_device(device)
+            .require_grad()
+    };
+    let (tensor, other) = (random(), random());
+    let ops = config.ops.clone();
+
+    Box::new(move || {
+        ops.iter()
+            .fold(tensor.clone(), |tensor, op| op.apply(tensor, &other))
+            .flatten(0, D - 1)
+    })
+}
@@ -0,0 +1,132 @@
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use burn::{
+    config::Config,
+    tensor::{backend::Backend, Distribution, Tensor},
+};
+use criterion::Criterion;
+
+pub struct MatmulBenchSuite;
+
+impl BenchSuite for MatmulBenchSuite {
+    type Config = MatmulConfig;
+
+    fn name() -> String {
+        "matmul".into()
+    }
+
+    fn description() -> String {
+        "Matrix multiplication benchmarks.".into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let mut label = format!("m{}-k{}-n{}", config.m, config.k, config.n);
+
+        if !config.batch.is_empty() {
+            let batch: Vec<_> = config.batch.iter().map(usize::to_string).collect();
+            label = format!("b{}-{label}", batch.join("x"));
+        }
+        if config.transpose_lhs {
+            label += "-tl";
+        }
+        if config.transpose_rhs {
+            label += "-tr";
+        }
+
+        label
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        match config.batch.len() {
+            0..=2 => Ok(()),
+            _ => Err("at most 2 batch dimensions are supported".into()),
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        vec![
+            // Square
+            MatmulConfig::new(vec![], 512, 512, 512),
+            MatmulConfig::new(vec![], 1024, 1024, 1024),
+            // Tall-skinny
+            MatmulConfig::new(vec![], 65536, 64, 64),
+            MatmulConfig::new(vec![], 64, 65536, 64),
+            // Batched, e.g. attention scores with and without the heads
+            MatmulConfig::new(vec![32], 128, 64, 128),
+            MatmulConfig::new(vec![8, 12], 128, 64, 128),
+            // Transposed operands
+            MatmulConfig::new(vec![], 1024, 1024, 1024).with_transpose_lhs(true),
+            MatmulConfig::new(vec![], 1024, 1024, 1024).with_transpose_rhs(true),
+        ]
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(c, &Self::name(), &Self::configs(), &MatmulBench, device);
+    }
+}
+
+#[derive(Config)]
+pub struct MatmulConfig {
+    /// Batch dimensions, empty for a 2D matmul.
+    pub batch: Vec<usize>,
+    pub m: usize,
+    pub k: usize,
+    pub n: usize,
+    /// Whether the left operand is stored as `[k, m]` and transposed before the matmul.
+    #[config(default = false)]
+    pub transpose_lhs: bool,
+    /// Whether the right operand is stored as `[n, k]` and transposed before the matmul.
+    #[config(default = false)]
+    pub transpose_rhs: bool,
+}
+
+pub struct MatmulBench;
+
+impl<B: Backend> Bench<B> for MatmulBench {
+    type Config = MatmulConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        match config.batch.len() {
+            0 => prepare::<B, 2>(config, device),
+            1 => prepare::<B, 3>(config, device),
+            2 => prepare::<B, 4>(config, device),
+            _ => unreachable!("The number of batch dimensions is validated by the suite"),
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let batch: usize = config.batch.iter().product();
+
+        Some(Work::Flops(
+            (2 * batch * config.m * config.k * config.n) as u64,
+        ))
+    }
+}
+
+fn prepare<B: Backend, const D: usize>(config: &MatmulConfig, device: &B::Device) -> BenchFunc<B> {
+    let random = |rows: usize, cols: usize, transpose: bool| {
+        let mut shape = [0; D];
+        shape[..D - 2].copy_from_slice(&config.batch);
+        shape[D - 2..].copy_from_slice(&match transpose {
+            true => [cols, rows],
+            false => [rows, cols],
+        });
+
+        Tensor::<B, D>::random(shape, Distribution::Uniform(-1.0, 1.0))
+            .to_device(device)
+            .require_grad()
+    };
+    let operand = move |tensor: &Tensor<B, D>, transpose: bool| match transpose {
+        true => tensor.clone().transpose(),
+        false => tensor.clone(),
+    };
+
+    let (transpose_lhs, transpose_rhs) = (config.transpose_lhs, config.transpose_rhs);
+    let lhs = random(config.m, config.k, transpose_lhs);
+    let rhs = random(config.k, config.n, transpose_rhs);
+
+    Box::new(move || {
+        operand(&lhs, transpose_lhs)
+            .matmul(operand(&rhs, transpose_rhs))
+            .flatten(0, D - 1)
+    })
+}
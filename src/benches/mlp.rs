@@ -1,8 +1,4 @@
-use crate::{
-    bench::{run_benchmark, Bench, BenchFunc, BenchSuite},
-    device, BenchBackend,
-};
-use burn::backend::Autodiff;
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
 use burn::{
     config::Config,
     module::Module,
@@ -14,76 +10,56 @@ use criterion::Criterion;
 pub struct MlpBenchSuite;
 
 impl BenchSuite for MlpBenchSuite {
+    type Config = MlpConfig;
+
     fn name() -> String {
         "mlp".into()
     }
 
-    fn details() -> String {
-        let mut details = String::from("Multi layer perceptron (MLP) benchmarks.\n\n");
-
-        configs()
-            .into_iter()
-            .enumerate()
-            .for_each(|(i, config)| details += format!("- {} => `{}`\n", i + 1, config).as_str());
-
-        details
+    fn description() -> String {
+        "Multi layer perceptron (MLP) benchmarks.".into()
     }
 
-    fn run(c: &mut Criterion) {
-        let name = Self::name();
-        let name_autodiff = format!("{}-autodiff", Self::name());
+    fn label(config: &Self::Config) -> String {
+        format!(
+            "b{}-l{}-d{}",
+            config.batch_size, config.num_layers, config.d_model
+        )
+    }
 
-        run_benchmark(c, &name, configs(), MlpBench::new());
-        run_benchmark(c, &name_autodiff, configs(), MlpBenchAD::new());
+    fn default_configs() -> Vec<Self::Config> {
+        vec![
+            MlpConfig::new(1, 100, 32),
+            MlpConfig::new(32, 4, 1024),
+            MlpConfig::new(128, 8, 2048),
+        ]
     }
-}
 
-fn configs() -> Vec<MlpConfig> {
-    vec![
-        MlpConfig::new(1, 100, 32),
-        MlpConfig::new(32, 4, 1024),
-        MlpConfig::new(128, 8, 2048),
-    ]
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(c, &Self::name(), &Self::configs(), &MlpBench, device);
+    }
 }
 
-#[derive(new)]
 pub struct MlpBench;
-#[derive(new)]
-pub struct MlpBenchAD;
 
-impl Bench for MlpBench {
+impl<B: Backend> Bench<B> for MlpBench {
     type Config = MlpConfig;
 
-    fn prepare(&self, config: &Self::Config) -> BenchFunc {
-        let device = device();
-        let tensor =
-            Tensor::<BenchBackend, 2>::ones([config.batch_size, config.d_model]).to_device(&device);
-        let mlp = Mlp::new(config).to_device(&device);
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let tensor = Tensor::<B, 2>::ones([config.batch_size, config.d_model]).to_device(device);
+        let mlp = Mlp::new(config).to_device(device);
 
-        Box::new(move || {
-            let tensor = mlp.forward(tensor.clone());
-            <BenchBackend as Backend>::sync(&device);
-        })
+        Box::new(move || mlp.forward(tensor.clone()).flatten(0, 1))
     }
-}
 
-impl Bench for MlpBenchAD {
-    type Config = MlpConfig;
-
-    fn prepare(&self, config: &Self::Config) -> BenchFunc {
-        type ADBackend = Autodiff<BenchBackend>;
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let linear = 2 * config.batch_size * config.d_model * config.d_model;
 
-        let device = device();
-        let tensor =
-            Tensor::<ADBackend, 2>::ones([config.batch_size, config.d_model]).to_device(&device);
-        let mlp = Mlp::new(config).to_device(&device);
+        Some(Work::Flops((config.num_layers * linear) as u64))
+    }
 
-        Box::new(move || {
-            let tensor = mlp.forward(tensor.clone());
-            let loss = tensor.sum();
-            let _grads = loss.backward();
-            <BenchBackend as Backend>::sync(&device);
-        })
+    fn loss(&self, output: Tensor<B, 1>) -> Tensor<B, 1> {
+        output.sum()
     }
 }
 
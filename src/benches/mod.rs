@@ -1,3 +1,18 @@
+pub mod attention;
+pub mod conv1d;
 pub mod conv2d;
+pub mod conv_transpose2d;
+pub mod data_movement;
+pub mod decoder;
+pub mod elementwise;
+pub mod matmul;
 pub mod mlp;
+pub mod models;
+pub mod norm;
+pub mod optimizer;
+pub mod pool2d;
+pub mod record;
+pub mod recurrent;
+pub mod reduction;
+pub mod training;
 pub mod transformer;
@@ -0,0 +1,236 @@
+use crate::bench::{
+    run_benchmark, run_benchmark_variants, training_step, Bench, BenchFunc, BenchSuite, Work,
+};
+use burn::{
+    backend::Autodiff,
+    config::Config,
+    module::Module,
+    optim::AdamWConfig,
+    tensor::{
+        backend::{AutodiffBackend, Backend},
+        Distribution, Tensor,
+    },
+};
+use criterion::Criterion;
+
+mod gpt;
+mod resnet;
+mod vit;
+
+pub use gpt::{Gpt, GptConfig};
+pub use resnet::{ResNet, ResNetConfig};
+pub use vit::{Vit, VitConfig};
+
+/// Number of classes of the image classifiers, as ImageNet.
+const NUM_CLASSES: usize = 1000;
+/// Height and width of the images.
+const IMAGE_SIZE: usize = 224;
+/// Learning rate of the optimizer of the training steps.
+const LEARNING_RATE: f64 = 1e-3;
+
+pub struct ModelsBenchSuite;
+
+impl BenchSuite for ModelsBenchSuite {
+    type Config = ModelsConfig;
+
+    fn name() -> String {
+        "models".into()
+    }
+
+    fn description() -> String {
+        "End-to-end models benchmarks: a ResNet-18, a ViT-Tiny and a small GPT. The forward \
+         pass is the inference and the autodiff variant executes the backward pass from the logits, \
+         without the optimizer. The training variant is a full training step: the mean squared \
+         error of the logits, the backward pass and the update of an AdamW optimizer."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        format!("{}-b{}", config.model.name(), config.batch_size)
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        vec![
+            ModelsConfig::new(ModelKind::ResNet18, 1),
+            ModelsConfig::new(ModelKind::ResNet18, 32),
+            ModelsConfig::new(ModelKind::VitTiny, 1),
+            ModelsConfig::new(ModelKind::VitTiny, 32),
+            ModelsConfig::new(ModelKind::GptSmall, 1),
+            ModelsConfig::new(ModelKind::GptSmall, 8),
+        ]
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        let configs = Self::configs();
+
+        run_benchmark_variants::<B, _, _>(c, &Self::name(), &configs, &ModelsBench, device);
+        run_benchmark::<Autodiff<B>, _, _>(
+            c,
+            &format!("{}-training", Self::name()),
+            &configs,
+            &ModelsTrainingBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct ModelsConfig {
+    pub model: ModelKind,
+    pub batch_size: usize,
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum ModelKind {
+    /// ResNet-18 on 224x224 images.
+    ResNet18,
+    /// ViT-Tiny with 16x16 patches on 224x224 images.
+    VitTiny,
+    /// GPT with 8 layers of 512 features, on sequences of 256 tokens.
+    GptSmall,
+}
+
+impl ModelKind {
+    fn name(&self) -> &'static str {
+        match self {
+            ModelKind::ResNet18 => "resnet18",
+            ModelKind::VitTiny => "vit_tiny",
+            ModelKind::GptSmall => "gpt_small",
+        }
+    }
+}
+
+fn resnet18() -> ResNetConfig {
+    ResNetConfig::new([2, 2, 2, 2], NUM_CLASSES, IMAGE_SIZE)
+}
+
+fn vit_tiny() -> VitConfig {
+    VitConfig::new(IMAGE_SIZE, 16, 192, 3, 12, NUM_CLASSES)
+}
+
+fn gpt_small() -> GptConfig {
+    GptConfig::new(16384, 256, 512, 8, 8)
+}
+
+pub struct ModelsBench;
+
+impl<B: Backend> Bench<B> for ModelsBench {
+    type Config = ModelsConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let images = || {
+            Tensor::<B, 4>::random(
+                [config.batch_size, 3, IMAGE_SIZE, IMAGE_SIZE],
+                Distribution::Default,
+            )
+            .to_device(device)
+        };
+
+        match config.model {
+            ModelKind::ResNet18 => {
+                let model = resnet18().init().to_device(device);
+                let images = images();
+
+                Box::new(move || model.forward(images.clone()).flatten(0, 1))
+            }
+            ModelKind::VitTiny => {
+                let model = vit_tiny().init().to_device(device);
+                let images = images();
+
+                Box::new(move || model.forward(images.clone()).flatten(0, 1))
+            }
+            ModelKind::GptSmall => {
+                let gpt = gpt_small();
+                let model = gpt.init().to_device(device);
+                let tokens = Tensor::<B, 2>::random(
+                    [config.batch_size, gpt.seq_length],
+                    Distribution::Uniform(0.0, gpt.vocab_size as f64),
+                )
+                .to_device(device)
+                .int();
+
+                Box::new(move || model.forward(tokens.clone()).flatten(0, 2))
+            }
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        Some(Work::Flops(flops(config)))
+    }
+}
+
+/// The training step of the models, on the autodiff backend.
+pub struct ModelsTrainingBench;
+
+impl<B: AutodiffBackend> Bench<B> for ModelsTrainingBench {
+    type Config = ModelsConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let images = || {
+            Tensor::<B, 4>::random(
+                [config.batch_size, 3, IMAGE_SIZE, IMAGE_SIZE],
+                Distribution::Default,
+            )
+            .to_device(device)
+        };
+
+        match config.model {
+            ModelKind::ResNet18 => {
+                let model = resnet18().init().to_device(device);
+                let images = images();
+
+                training_step(
+                    model,
+                    AdamWConfig::new().init::<B, ResNet<B>>(),
+                    LEARNING_RATE,
+                    move |model: &ResNet<B>| model.forward(images.clone()),
+                )
+            }
+            ModelKind::VitTiny => {
+                let model = vit_tiny().init().to_device(device);
+                let images = images();
+
+                training_step(
+                    model,
+                    AdamWConfig::new().init::<B, Vit<B>>(),
+                    LEARNING_RATE,
+                    move |model: &Vit<B>| model.forward(images.clone()),
+                )
+            }
+            ModelKind::GptSmall => {
+                let gpt = gpt_small();
+                let model = gpt.init().to_device(device);
+                let tokens = Tensor::<B, 2>::random(
+                    [config.batch_size, gpt.seq_length],
+                    Distribution::Uniform(0.0, gpt.vocab_size as f64),
+                )
+                .to_device(device)
+                .int();
+
+                training_step(
+                    model,
+                    AdamWConfig::new().init::<B, Gpt<B>>(),
+                    LEARNING_RATE,
+                    move |model: &Gpt<B>| model.forward(tokens.clone()),
+                )
+            }
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        // The backward pass does about twice the operations of the forward pass, and the update
+        // of the optimizer is negligible.
+        Some(Work::Flops(3 * flops(config)))
+    }
+}
+
+/// Floating point operations of the forward pass.
+fn flops(config: &ModelsConfig) -> u64 {
+    let flops = match config.model {
+        ModelKind::ResNet18 => resnet18().flops(config.batch_size),
+        ModelKind::VitTiny => vit_tiny().flops(config.batch_size),
+        ModelKind::GptSmall => gpt_small().flops(config.batch_size),
+    };
+
+    flops as u64
+}
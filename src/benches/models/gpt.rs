@@ -0,0 +1,83 @@
+use burn::{
+    config::Config,
+    module::Module,
+    nn::attention::generate_autoregressive_mask,
+    nn::transformer::{TransformerEncoder, TransformerEncoderConfig, TransformerEncoderInput},
+    nn::{Embedding, EmbeddingConfig, LayerNorm, LayerNormConfig, Linear, LinearConfig},
+    tensor::{backend::Backend, Int, Tensor},
+};
+
+#[derive(Config)]
+pub struct GptConfig {
+    pub vocab_size: usize,
+    pub seq_length: usize,
+    pub d_model: usize,
+    pub n_heads: usize,
+    pub n_layers: usize,
+}
+
+impl GptConfig {
+    pub fn init<B: Backend>(&self) -> Gpt<B> {
+        Gpt {
+            token_embedding: EmbeddingConfig::new(self.vocab_size, self.d_model).init(),
+            position_embedding: EmbeddingConfig::new(self.seq_length, self.d_model).init(),
+            decoder: TransformerEncoderConfig::new(
+                self.d_model,
+                4 * self.d_model,
+                self.n_heads,
+                self.n_layers,
+            )
+            .with_dropout(0.0)
+            .with_norm_first(true)
+            .init(),
+            norm: LayerNormConfig::new(self.d_model).init(),
+            head: LinearConfig::new(self.d_model, self.vocab_size)
+                .with_bias(false)
+                .init(),
+        }
+    }
+
+    /// Floating point operations of the forward pass, counting the matrix multiplications.
+    ///
+    /// The causal attention is counted as a full one, as the masked scores are still computed.
+    pub fn flops(&self, batch_size: usize) -> usize {
+        let tokens = batch_size * self.seq_length;
+        // Query, key, value and output projections, attention and feed forward.
+        let layer = 4 * 2 * tokens * self.d_model * self.d_model
+            + 2 * 2 * tokens * self.seq_length * self.d_model
+            + 2 * 2 * tokens * self.d_model * 4 * self.d_model;
+
+        self.n_layers * layer + 2 * tokens * self.d_model * self.vocab_size
+    }
+}
+
+/// A GPT-style language model, i.e. a stack of causal self-attention blocks.
+#[derive(Module, Debug)]
+pub struct Gpt<B: Backend> {
+    token_embedding: Embedding<B>,
+    position_embedding: Embedding<B>,
+    decoder: TransformerEncoder<B>,
+    norm: LayerNorm<B>,
+    head: Linear<B>,
+}
+
+impl<B: Backend> Gpt<B> {
+    /// Predict the next token of each position of the tokens of shape `[batch_size, seq_length]`,
+    /// returning the logits.
+    pub fn forward(&self, tokens: Tensor<B, 2, Int>) -> Tensor<B, 3> {
+        let [batch_size, seq_length] = tokens.dims();
+        let device = tokens.device();
+        let positions = Tensor::arange(0..seq_length)
+            .to_device(&device)
+            .reshape([1, seq_length])
+            .repeat(0, batch_size);
+        let x = self.token_embedding.forward(tokens) + self.position_embedding.forward(positions);
+
+        let mask = generate_autoregressive_mask::<B>(batch_size, seq_length, &device);
+        let x = self
+            .decoder
+            .forward(TransformerEncoderInput::new(x).mask_attn(mask));
+
+        self.head.forward(self.norm.forward(x))
+    }
+}
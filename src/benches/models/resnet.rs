@@ -0,0 +1,162 @@
+use burn::{
+    config::Config,
+    module::Module,
+    nn::conv::{Conv2d, Conv2dConfig},
+    nn::{BatchNorm, BatchNormConfig, Linear, LinearConfig, PaddingConfig2d},
+    tensor::{
+        activation::relu,
+        backend::Backend,
+        module::{adaptive_avg_pool2d, max_pool2d},
+        Tensor,
+    },
+};
+
+/// Number of channels of each stage.
+const CHANNELS: [usize; 4] = [64, 128, 256, 512];
+
+#[derive(Config)]
+pub struct ResNetConfig {
+    /// Number of basic blocks of each stage, e.g. `[2, 2, 2, 2]` for ResNet-18.
+    pub blocks: [usize; 4],
+    pub num_classes: usize,
+    pub image_size: usize,
+}
+
+impl ResNetConfig {
+    pub fn init<B: Backend>(&self) -> ResNet<B> {
+        let mut blocks = Vec::new();
+        let mut channels_in = CHANNELS[0];
+
+        for (stage, (&num_blocks, &channels)) in self.blocks.iter().zip(CHANNELS.iter()).enumerate()
+        {
+            for block in 0..num_blocks {
+                // The first block of each stage but the first one halves the resolution.
+                let stride = if stage > 0 && block == 0 { 2 } else { 1 };
+                blocks.push(BasicBlock::new(channels_in, channels, stride));
+                channels_in = channels;
+            }
+        }
+
+        ResNet {
+            conv: conv(3, CHANNELS[0], 7, 2).init(),
+            norm: BatchNormConfig::new(CHANNELS[0]).init(),
+            blocks,
+            fc: LinearConfig::new(CHANNELS[3], self.num_classes).init(),
+        }
+    }
+
+    /// Floating point operations of the forward pass, counting the convolutions and the
+    /// classifier.
+    pub fn flops(&self, batch_size: usize) -> usize {
+        let conv_flops = |channels_in, channels_out, kernel_size, size: usize| {
+            2 * batch_size * channels_out * size * size * channels_in * kernel_size * kernel_size
+        };
+        // The stem convolution and the max pooling both halve the resolution.
+        let mut size = self.image_size / 2;
+        let mut flops = conv_flops(3, CHANNELS[0], 7, size);
+        size /= 2;
+        let mut channels_in = CHANNELS[0];
+
+        for (stage, (&num_blocks, &channels)) in self.blocks.iter().zip(CHANNELS.iter()).enumerate()
+        {
+            for block in 0..num_blocks {
+                if stage > 0 && block == 0 {
+                    size /= 2;
+                    flops += conv_flops(channels_in, channels, 1, size);
+                }
+                flops += conv_flops(channels_in, channels, 3, size);
+                flops += conv_flops(channels, channels, 3, size);
+                channels_in = channels;
+            }
+        }
+
+        flops + 2 * batch_size * CHANNELS[3] * self.num_classes
+    }
+}
+
+/// A ResNet with basic blocks, as ResNet-18 and ResNet-34.
+#[derive(Module, Debug)]
+pub struct ResNet<B: Backend> {
+    conv: Conv2d<B>,
+    norm: BatchNorm<B, 2>,
+    blocks: Vec<BasicBlock<B>>,
+    fc: Linear<B>,
+}
+
+impl<B: Backend> ResNet<B> {
+    /// Classify the images of shape `[batch_size, 3, height, width]`, returning the logits.
+    pub fn forward(&self, images: Tensor<B, 4>) -> Tensor<B, 2> {
+        let x = relu(self.norm.forward(self.conv.forward(images)));
+        let mut x = max_pool2d(x, [3, 3], [2, 2], [1, 1], [1, 1]);
+
+        for block in self.blocks.iter() {
+            x = block.forward(x);
+        }
+
+        let x = adaptive_avg_pool2d(x, [1, 1]).flatten(1, 3);
+
+        self.fc.forward(x)
+    }
+}
+
+#[derive(Module, Debug)]
+pub struct BasicBlock<B: Backend> {
+    conv1: Conv2d<B>,
+    norm1: BatchNorm<B, 2>,
+    conv2: Conv2d<B>,
+    norm2: BatchNorm<B, 2>,
+    /// Projection of the residual, when the block changes its shape.
+    downsample: Option<Downsample<B>>,
+}
+
+impl<B: Backend> BasicBlock<B> {
+    fn new(channels_in: usize, channels_out: usize, stride: usize) -> Self {
+        let downsample = (stride != 1 || channels_in != channels_out).then(|| Downsample {
+            conv: conv(channels_in, channels_out, 1, stride).init(),
+            norm: BatchNormConfig::new(channels_out).init(),
+        });
+
+        Self {
+            conv1: conv(channels_in, channels_out, 3, stride).init(),
+            norm1: BatchNormConfig::new(channels_out).init(),
+            conv2: conv(channels_out, channels_out, 3, 1).init(),
+            norm2: BatchNormConfig::new(channels_out).init(),
+            downsample,
+        }
+    }
+
+    fn forward(&self, tensor: Tensor<B, 4>) -> Tensor<B, 4> {
+        let residual = match &self.downsample {
+            Some(downsample) => downsample
+                .norm
+                .forward(downsample.conv.forward(tensor.clone())),
+            None => tensor.clone(),
+        };
+        let x = relu(self.norm1.forward(self.conv1.forward(tensor)));
+        let x = self.norm2.forward(self.conv2.forward(x));
+
+        relu(x + residual)
+    }
+}
+
+#[derive(Module, Debug)]
+pub struct Downsample<B: Backend> {
+    conv: Conv2d<B>,
+    norm: BatchNorm<B, 2>,
+}
+
+/// A convolution without bias, followed by a batch norm, padded to divide the resolution by the
+/// stride.
+fn conv(
+    channels_in: usize,
+    channels_out: usize,
+    kernel_size: usize,
+    stride: usize,
+) -> Conv2dConfig {
+    let padding = kernel_size / 2;
+
+    Conv2dConfig::new([channels_in, channels_out], [kernel_size, kernel_size])
+        .with_stride([stride, stride])
+        .with_padding(PaddingConfig2d::Explicit(padding, padding))
+        .with_bias(false)
+}
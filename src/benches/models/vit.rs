@@ -0,0 +1,98 @@
+use burn::{
+    config::Config,
+    module::{Module, Param},
+    nn::conv::{Conv2d, Conv2dConfig},
+    nn::transformer::{TransformerEncoder, TransformerEncoderConfig, TransformerEncoderInput},
+    nn::{LayerNorm, LayerNormConfig, Linear, LinearConfig},
+    tensor::{backend::Backend, Distribution, Tensor},
+};
+
+#[derive(Config)]
+pub struct VitConfig {
+    pub image_size: usize,
+    pub patch_size: usize,
+    pub d_model: usize,
+    pub n_heads: usize,
+    pub n_layers: usize,
+    pub num_classes: usize,
+}
+
+impl VitConfig {
+    /// Number of tokens of the encoder, the class token followed by the patches.
+    fn seq_length(&self) -> usize {
+        (self.image_size / self.patch_size).pow(2) + 1
+    }
+
+    pub fn init<B: Backend>(&self) -> Vit<B> {
+        let embedding = |shape| Param::from(Tensor::random(shape, Distribution::Normal(0.0, 0.02)));
+
+        Vit {
+            patch_embedding: Conv2dConfig::new(
+                [3, self.d_model],
+                [self.patch_size, self.patch_size],
+            )
+            .with_stride([self.patch_size, self.patch_size])
+            .init(),
+            class_token: embedding([1, 1, self.d_model]),
+            position_embedding: embedding([1, self.seq_length(), self.d_model]),
+            encoder: TransformerEncoderConfig::new(
+                self.d_model,
+                4 * self.d_model,
+                self.n_heads,
+                self.n_layers,
+            )
+            .with_dropout(0.0)
+            .with_norm_first(true)
+            .init(),
+            norm: LayerNormConfig::new(self.d_model).init(),
+            head: LinearConfig::new(self.d_model, self.num_classes).init(),
+        }
+    }
+
+    /// Floating point operations of the forward pass, counting the matrix multiplications.
+    pub fn flops(&self, batch_size: usize) -> usize {
+        let seq_length = self.seq_length();
+        let tokens = batch_size * seq_length;
+        let patches = 2 * (tokens - batch_size) * self.d_model * 3 * self.patch_size.pow(2);
+        // Query, key, value and output projections, attention and feed forward.
+        let layer = 4 * 2 * tokens * self.d_model * self.d_model
+            + 2 * 2 * tokens * seq_length * self.d_model
+            + 2 * 2 * tokens * self.d_model * 4 * self.d_model;
+
+        patches + self.n_layers * layer + 2 * batch_size * self.d_model * self.num_classes
+    }
+}
+
+/// A vision transformer classifying the images from their class token.
+#[derive(Module, Debug)]
+pub struct Vit<B: Backend> {
+    patch_embedding: Conv2d<B>,
+    class_token: Param<Tensor<B, 3>>,
+    position_embedding: Param<Tensor<B, 3>>,
+    encoder: TransformerEncoder<B>,
+    norm: LayerNorm<B>,
+    head: Linear<B>,
+}
+
+impl<B: Backend> Vit<B> {
+    /// Classify the images of shape `[batch_size, 3, height, width]`, returning the logits.
+    pub fn forward(&self, images: Tensor<B, 4>) -> Tensor<B, 2> {
+        let [batch_size, _, _, _] = images.dims();
+        let patches = self
+            .patch_embedding
+            .forward(images)
+            .flatten::<3>(2, 3)
+            .swap_dims(1, 2);
+        let class_token = self.class_token.val().repeat(0, batch_size);
+        let x = Tensor::cat(vec![class_token, patches], 1) + self.position_embedding.val();
+
+        let x = self.encoder.forward(TransformerEncoderInput::new(x));
+        let [_, _, d_model] = x.dims();
+        let class_token = self
+            .norm
+            .forward(x.slice([0..batch_size, 0..1]))
+            .reshape([batch_size, d_model]);
+
+        self.head.forward(class_token)
+    }
+}
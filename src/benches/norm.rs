@@ -0,0 +1,160 @@
+use crate::bench::{run_benchmark, run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use burn::{
+    backend::Autodiff,
+    config::Config,
+    module::Module,
+    nn::{BatchNorm, BatchNormConfig, GroupNormConfig, LayerNormConfig},
+    tensor::{backend::Backend, Distribution, Tensor},
+};
+use criterion::Criterion;
+
+pub struct NormBenchSuite;
+
+impl BenchSuite for NormBenchSuite {
+    type Config = NormConfig;
+
+    fn name() -> String {
+        "norm".into()
+    }
+
+    fn description() -> String {
+        "Normalization layers benchmarks. The forward pass runs in inference mode, the training \
+         variant runs the forward pass in training mode on the autodiff backend and the autodiff \
+         variant adds the backward pass. The modes only differ for the batch norm."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let norm = match config.norm {
+            NormKind::Layer => "layer".to_string(),
+            NormKind::Batch => "batch".to_string(),
+            NormKind::Group(num_groups) => format!("group{num_groups}"),
+        };
+
+        format!(
+            "{norm}-b{}-c{}-{}x{}",
+            config.batch_size, config.channels, config.height, config.width
+        )
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        match config.norm {
+            NormKind::Group(0) => Err("at least one group is expected".into()),
+            NormKind::Group(num_groups) if config.channels % num_groups != 0 => Err(format!(
+                "the {} channels must be a multiple of the {num_groups} groups",
+                config.channels
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        [NormKind::Layer, NormKind::Batch, NormKind::Group(32)]
+            .into_iter()
+            .flat_map(|norm| {
+                [
+                    NormConfig::new(norm.clone(), 32, 64, 56, 56),
+                    NormConfig::new(norm.clone(), 16, 512, 14, 14),
+                    // Transformer activations, with 512 tokens of 768 features.
+                    NormConfig::new(norm, 8, 768, 1, 512),
+                ]
+            })
+            .collect()
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        let configs = Self::configs();
+
+        run_benchmark_variants::<B, _, _>(c, &Self::name(), &configs, &NormBench, device);
+        run_benchmark::<Autodiff<B>, _, _>(
+            c,
+            &format!("{}-training", Self::name()),
+            &configs,
+            &NormBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct NormConfig {
+    pub norm: NormKind,
+    pub batch_size: usize,
+    pub channels: usize,
+    pub height: usize,
+    pub width: usize,
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum NormKind {
+    /// Layer norm over the channels, the input being laid out as `[batch, tokens, channels]`.
+    Layer,
+    /// 2D batch norm.
+    Batch,
+    /// Group norm with the given number of groups.
+    Group(usize),
+}
+
+pub struct NormBench;
+
+impl<B: Backend> Bench<B> for NormBench {
+    type Config = NormConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let tensor = || {
+            Tensor::<B, 4>::random(
+                [
+                    config.batch_size,
+                    config.channels,
+                    config.height,
+                    config.width,
+                ],
+                Distribution::Default,
+            )
+            .to_device(device)
+        };
+
+        match config.norm {
+            NormKind::Layer => {
+                let tensor = Tensor::<B, 3>::random(
+                    [
+                        config.batch_size,
+                        config.height * config.width,
+                        config.channels,
+                    ],
+                    Distribution::Default,
+                )
+                .to_device(device);
+                let norm = LayerNormConfig::new(config.channels)
+                    .init()
+                    .to_device(device);
+
+                Box::new(move || norm.forward(tensor.clone()).flatten(0, 2))
+            }
+            NormKind::Batch => {
+                let tensor = tensor();
+                let norm: BatchNorm<B, 2> = BatchNormConfig::new(config.channels)
+                    .init()
+                    .to_device(device);
+
+                Box::new(move || norm.forward(tensor.clone()).flatten(0, 3))
+            }
+            NormKind::Group(num_groups) => {
+                let tensor = tensor();
+                let norm = GroupNormConfig::new(num_groups, config.channels)
+                    .init()
+                    .to_device(device);
+
+                Box::new(move || norm.forward(tensor.clone()).flatten(0, 3))
+            }
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let elements = config.batch_size * config.channels * config.height * config.width;
+        // The input is read and the output written, the statistics are negligible.
+        let bytes = 2 * elements * std::mem::size_of::<B::FloatElem>();
+
+        Some(Work::Bytes(bytes as u64))
+    }
+}
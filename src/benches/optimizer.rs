@@ -0,0 +1,197 @@
+use crate::bench::{run_benchmark_batched, BatchedBench, BenchSuite, RoutineFunc, SetupFunc, Work};
+use crate::training::OptimizerKind;
+use burn::{
+    backend::Autodiff,
+    config::Config,
+    module::{Module, Param},
+    optim::{AdamConfig, AdamWConfig, GradientsParams, Optimizer, SgdConfig},
+    tensor::{
+        backend::{AutodiffBackend, Backend},
+        Distribution, Tensor,
+    },
+};
+use criterion::Criterion;
+use std::{cell::RefCell, rc::Rc};
+
+pub struct OptimizerBenchSuite;
+
+impl BenchSuite for OptimizerBenchSuite {
+    type Config = OptimizerConfig;
+
+    fn name() -> String {
+        "optimizer".into()
+    }
+
+    fn description() -> String {
+        "Optimizer step benchmarks on modules made of parameters of the same size, from many \
+         small ones to a few huge ones. The gradients are computed before each step and aren't \
+         measured, and the throughput counts the updated weights, the elements of the \
+         parameters."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        format!(
+            "{}-p{}-s{}",
+            config.optimizer.name(),
+            config.num_params,
+            config.param_size
+        )
+    }
+
+    fn element() -> Option<&'static str> {
+        Some("weight")
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        match (config.num_params, config.param_size) {
+            (0, _) => Err("at least one parameter is expected".into()),
+            (_, 0) => Err("the parameters must have at least one element".into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        [
+            OptimizerKind::Sgd,
+            OptimizerKind::Adam,
+            OptimizerKind::AdamW,
+        ]
+        .into_iter()
+        .flat_map(|optimizer| {
+            [
+                OptimizerConfig::new(optimizer.clone(), 10000, 64),
+                OptimizerConfig::new(optimizer.clone(), 1000, 4096),
+                OptimizerConfig::new(optimizer, 8, 4 * 1024 * 1024),
+            ]
+        })
+        .collect()
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_batched::<Autodiff<B>, _, _>(
+            c,
+            &Self::name(),
+            &Self::configs(),
+            &OptimizerBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct OptimizerConfig {
+    pub optimizer: OptimizerKind,
+    pub num_params: usize,
+    /// Number of elements of each parameter.
+    pub param_size: usize,
+    #[config(default = 1e-3)]
+    pub learning_rate: f64,
+}
+
+/// A module only made of parameters.
+#[derive(Module, Debug)]
+pub struct Params<B: Backend> {
+    params: Vec<Param<Tensor<B, 1>>>,
+}
+
+impl<B: Backend> Params<B> {
+    pub fn new(config: &OptimizerConfig, device: &B::Device) -> Self {
+        let params = (0..config.num_params)
+            .map(|_| {
+                let tensor = Tensor::random([config.param_size], Distribution::Default);
+                Param::from(tensor.to_device(device))
+            })
+            .collect();
+
+        Self { params }
+    }
+
+    /// The sum of the parameters, whose gradient is one for every parameter.
+    pub fn forward(&self) -> Tensor<B, 1> {
+        self.params
+            .iter()
+            .map(|param| param.val().sum())
+            .reduce(|sum, param| sum + param)
+            .expect("The module should have parameters")
+    }
+}
+
+pub struct OptimizerBench;
+
+impl<B: AutodiffBackend> BatchedBench<B> for OptimizerBench {
+    type Config = OptimizerConfig;
+    type State = GradientsParams;
+
+    fn prepare(
+        &self,
+        config: &Self::Config,
+        device: &B::Device,
+    ) -> (SetupFunc<Self::State>, RoutineFunc<B, Self::State>) {
+        let params = Params::<B>::new(config, device);
+        let learning_rate = config.learning_rate;
+
+        match config.optimizer {
+            OptimizerKind::Sgd => step_funcs(
+                params,
+                SgdConfig::new().init::<B, Params<B>>(),
+                learning_rate,
+            ),
+            OptimizerKind::Adam => step_funcs(
+                params,
+                AdamConfig::new().init::<B, Params<B>>(),
+                learning_rate,
+            ),
+            OptimizerKind::AdamW => step_funcs(
+                params,
+                AdamWConfig::new().init::<B, Params<B>>(),
+                learning_rate,
+            ),
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        Some(Work::Elements(
+            (config.num_params * config.param_size) as u64,
+        ))
+    }
+}
+
+/// The functions computing the gradients of the module and updating it with the optimizer.
+///
+/// The module is shared, the step taking it and giving back its updated version.
+fn step_funcs<B, O>(
+    params: Params<B>,
+    mut optimizer: O,
+    learning_rate: f64,
+) -> (SetupFunc<GradientsParams>, RoutineFunc<B, GradientsParams>)
+where
+    B: AutodiffBackend,
+    O: Optimizer<Params<B>, B> + 'static,
+{
+    let module = Rc::new(RefCell::new(Some(params)));
+
+    let setup = {
+        let module = module.clone();
+
+        move || {
+            let module = module.borrow();
+            let params = module.as_ref().expect("The module should be given back");
+
+            GradientsParams::from_grads(params.forward().backward(), params)
+        }
+    };
+    let routine = move |grads: GradientsParams| {
+        let params = module
+            .borrow_mut()
+            .take()
+            .expect("The module should be given back");
+        let params = optimizer.step(learning_rate, params, grads);
+        let output = params.params[0].val();
+        *module.borrow_mut() = Some(params);
+
+        output
+    };
+
+    (Box::new(setup), Box::new(routine))
+}
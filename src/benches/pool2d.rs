@@ -0,0 +1,197 @@
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use burn::{
+    config::Config,
+    nn::pool::{AdaptiveAvgPool2dConfig, AvgPool2dConfig, MaxPool2dConfig},
+    tensor::{backend::Backend, Distribution, Tensor},
+};
+use criterion::Criterion;
+
+pub struct Pool2dBenchSuite;
+
+impl BenchSuite for Pool2dBenchSuite {
+    type Config = Pool2dConfig;
+
+    fn name() -> String {
+        "pool2d".into()
+    }
+
+    fn description() -> String {
+        "Max, average, adaptive average and adaptive max pooling benchmarks. Burn has no adaptive \
+         max pooling, so it is computed with a max over the slices of each window."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let pool = match config.pool {
+            PoolKind::Max(kernel_size, stride) => format!("max-k{kernel_size}-s{stride}"),
+            PoolKind::Avg(kernel_size, stride) => format!("avg-k{kernel_size}-s{stride}"),
+            PoolKind::AdaptiveAvg(output_size) => format!("adaptive_avg-o{output_size}"),
+            PoolKind::AdaptiveMax(output_size) => format!("adaptive_max-o{output_size}"),
+        };
+
+        format!(
+            "{pool}-b{}-c{}-{}x{}",
+            config.batch_size, config.channels, config.height, config.width
+        )
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        match config.pool.output_size(config.height, config.width) {
+            Some(_) => Ok(()),
+            None => Err("the kernel must fit in the input and the sizes must be positive".into()),
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        vec![
+            // ResNet stem.
+            Pool2dConfig::new(PoolKind::Max(3, 2), 8, 64, 112, 112),
+            Pool2dConfig::new(PoolKind::Max(2, 2), 8, 128, 56, 56),
+            Pool2dConfig::new(PoolKind::Avg(2, 2), 8, 128, 56, 56),
+            // Global pooling before the classifier.
+            Pool2dConfig::new(PoolKind::AdaptiveAvg(1), 8, 2048, 7, 7),
+            Pool2dConfig::new(PoolKind::AdaptiveAvg(7), 8, 512, 14, 14),
+            Pool2dConfig::new(PoolKind::AdaptiveMax(1), 8, 2048, 7, 7),
+            Pool2dConfig::new(PoolKind::AdaptiveMax(7), 8, 512, 14, 14),
+        ]
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(c, &Self::name(), &Self::configs(), &Pool2dBench, device);
+    }
+}
+
+#[derive(Config)]
+pub struct Pool2dConfig {
+    pub pool: PoolKind,
+    pub batch_size: usize,
+    pub channels: usize,
+    pub height: usize,
+    pub width: usize,
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum PoolKind {
+    /// Max pooling with the given square kernel size and stride, without padding.
+    Max(usize, usize),
+    /// Average pooling with the given square kernel size and stride, without padding.
+    Avg(usize, usize),
+    /// Adaptive average pooling to the given square output size.
+    AdaptiveAvg(usize),
+    /// Adaptive max pooling to the given square output size.
+    AdaptiveMax(usize),
+}
+
+impl PoolKind {
+    /// The spatial size of the output of the pooling, none when it has no output.
+    fn output_size(&self, height: usize, width: usize) -> Option<(usize, usize)> {
+        let size = |size: usize, kernel_size: usize, stride: usize| {
+            Some(size.checked_sub(kernel_size)?.checked_div(stride)? + 1)
+        };
+
+        match *self {
+            PoolKind::Max(kernel_size, stride) | PoolKind::Avg(kernel_size, stride)
+                if kernel_size > 0 =>
+            {
+                Some((
+                    size(height, kernel_size, stride)?,
+                    size(width, kernel_size, stride)?,
+                ))
+            }
+            PoolKind::AdaptiveAvg(output_size) | PoolKind::AdaptiveMax(output_size)
+                if output_size > 0 && height > 0 && width > 0 =>
+            {
+                Some((output_size, output_size))
+            }
+            _ => None,
+        }
+    }
+}
+
+pub struct Pool2dBench;
+
+impl<B: Backend> Bench<B> for Pool2dBench {
+    type Config = Pool2dConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        // The pooling has no parameters, so the gradient is computed for the input.
+        let tensor = Tensor::<B, 4>::random(
+            [
+                config.batch_size,
+                config.channels,
+                config.height,
+                config.width,
+            ],
+            Distribution::Default,
+        )
+        .to_device(device)
+        .require_grad();
+
+        match config.pool {
+            PoolKind::Max(kernel_size, stride) => {
+                let pool = MaxPool2dConfig::new([kernel_size, kernel_size])
+                    .with_strides([stride, stride])
+                    .init();
+
+                Box::new(move || pool.forward(tensor.clone()).flatten(0, 3))
+            }
+            PoolKind::Avg(kernel_size, stride) => {
+                let pool = AvgPool2dConfig::new([kernel_size, kernel_size])
+                    .with_strides([stride, stride])
+                    .init();
+
+                Box::new(move || pool.forward(tensor.clone()).flatten(0, 3))
+            }
+            PoolKind::AdaptiveAvg(output_size) => {
+                let pool = AdaptiveAvgPool2dConfig::new([output_size, output_size]).init();
+
+                Box::new(move || pool.forward(tensor.clone()).flatten(0, 3))
+            }
+            PoolKind::AdaptiveMax(output_size) => {
+                Box::new(move || adaptive_max_pool2d(tensor.clone(), output_size).flatten(0, 3))
+            }
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let (height, width) = config.pool.output_size(config.height, config.width)?;
+        let inputs = config.batch_size * config.channels * config.height * config.width;
+        let outputs = config.batch_size * config.channels * height * width;
+        // The input is read and the output written.
+        let bytes = (inputs + outputs) * std::mem::size_of::<B::FloatElem>();
+
+        Some(Work::Bytes(bytes as u64))
+    }
+}
+
+/// Adaptive max pooling of the images of shape `[batch_size, channels, height, width]` to the
+/// given square output size.
+///
+/// The maximum is separable, so the rows of each window are reduced first, then its columns. As
+/// in the adaptive average pooling, the window `i` of a dimension of size `n` spans from
+/// `floor(i * n / output_size)` to `ceil((i + 1) * n / output_size)`.
+fn adaptive_max_pool2d<B: Backend>(tensor: Tensor<B, 4>, output_size: usize) -> Tensor<B, 4> {
+    let [batch_size, channels, height, width] = tensor.dims();
+    let window =
+        |i: usize, size: usize| i * size / output_size..((i + 1) * size).div_ceil(output_size);
+
+    let rows = (0..output_size)
+        .map(|i| {
+            tensor
+                .clone()
+                .slice([0..batch_size, 0..channels, window(i, height), 0..width])
+                .max_dim(2)
+        })
+        .collect();
+    let rows = Tensor::cat(rows, 2);
+
+    let columns = (0..output_size)
+        .map(|j| {
+            rows.clone()
+                .slice([0..batch_size, 0..channels, 0..output_size, window(j, width)])
+                .max_dim(3)
+        })
+        .collect();
+
+    Tensor::cat(columns, 3)
+}
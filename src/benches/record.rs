@@ -0,0 +1,369 @@
+use crate::bench::{run_benchmark, Bench, BenchCase, BenchFunc, BenchSuite, Work};
+use crate::mlp::{Mlp, MlpConfig};
+use burn::{
+    config::Config,
+    module::Module,
+    nn::transformer::{TransformerEncoder, TransformerEncoderConfig, TransformerEncoderInput},
+    record::{
+        BinFileRecorder, BinGzFileRecorder, FileRecorder, FullPrecisionSettings,
+        HalfPrecisionSettings, JsonGzFileRecorder, NamedMpkFileRecorder, NamedMpkGzFileRecorder,
+        PrecisionSettings, PrettyJsonFileRecorder,
+    },
+    tensor::{backend::Backend, Tensor},
+};
+use criterion::Criterion;
+use tempfile::TempDir;
+
+/// Directory of the records, in the target directory instead of the temporary directory of the OS
+/// since it is often kept in memory, which wouldn't measure the disk.
+static RECORDS_DIR: &str = "target/records";
+/// Number of tokens of the transformer input, for the first inference.
+const SEQ_LENGTH: usize = 128;
+
+pub struct RecordBenchSuite;
+
+impl BenchSuite for RecordBenchSuite {
+    type Config = RecordConfig;
+
+    fn name() -> String {
+        "record".into()
+    }
+
+    fn description() -> String {
+        "Record serialization benchmarks with the file recorders: saving the model, loading \
+         it into an initialized model, and the startup of a service, i.e. the initialization, \
+         the loading and the first inference. The bandwidth is computed from the size of the \
+         parameters in the precision of the record."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let precision = match config.precision {
+            Precision::Full => "full",
+            Precision::Half => "half",
+        };
+
+        format!(
+            "{}-l{}-d{}-{}-{precision}",
+            config.model.name(),
+            config.num_layers,
+            config.d_model,
+            config.recorder.name(),
+        )
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        let recorders = [
+            RecorderKind::NamedMpk,
+            RecorderKind::NamedMpkGz,
+            RecorderKind::Bin,
+            RecorderKind::BinGz,
+            RecorderKind::PrettyJson,
+            RecorderKind::JsonGz,
+        ];
+        let mut configs: Vec<_> = recorders
+            .into_iter()
+            .map(|recorder| {
+                RecordConfig::new(RecordedModel::Mlp, 4, 1024, recorder, Precision::Full)
+            })
+            .collect();
+
+        for recorder in [RecorderKind::NamedMpk, RecorderKind::Bin] {
+            configs.push(RecordConfig::new(
+                RecordedModel::Mlp,
+                4,
+                1024,
+                recorder.clone(),
+                Precision::Half,
+            ));
+            configs.push(RecordConfig::new(
+                RecordedModel::Transformer,
+                6,
+                512,
+                recorder,
+                Precision::Full,
+            ));
+        }
+
+        // A large model, with 134M parameters.
+        configs.push(RecordConfig::new(
+            RecordedModel::Mlp,
+            32,
+            2048,
+            RecorderKind::NamedMpk,
+            Precision::Full,
+        ));
+
+        configs
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        let cases = Self::configs();
+
+        for (operation, bench) in [
+            ("save", RecordBench(RecordOperation::Save)),
+            ("load", RecordBench(RecordOperation::Load)),
+            ("startup", RecordBench(RecordOperation::Startup)),
+        ] {
+            run_benchmark::<B, _, _>(
+                c,
+                &format!("{}-{operation}", Self::name()),
+                &cases,
+                &bench,
+                device,
+            );
+        }
+    }
+}
+
+#[derive(Config)]
+pub struct RecordConfig {
+    pub model: RecordedModel,
+    pub num_layers: usize,
+    pub d_model: usize,
+    pub recorder: RecorderKind,
+    pub precision: Precision,
+}
+
+impl RecordConfig {
+    /// Number of parameters of the model.
+    fn num_params(&self) -> usize {
+        let d_model = self.d_model;
+        let layer = match self.model {
+            RecordedModel::Mlp => d_model * d_model + d_model,
+            // Attention projections, feed forward and norms.
+            RecordedModel::Transformer => 12 * d_model * d_model + 13 * d_model,
+        };
+
+        self.num_layers * layer
+    }
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum RecordedModel {
+    /// The [MLP](crate::mlp::Mlp).
+    Mlp,
+    /// A transformer encoder with 8 heads.
+    Transformer,
+}
+
+impl RecordedModel {
+    fn name(&self) -> &'static str {
+        match self {
+            RecordedModel::Mlp => "mlp",
+            RecordedModel::Transformer => "transformer",
+        }
+    }
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum RecorderKind {
+    NamedMpk,
+    NamedMpkGz,
+    Bin,
+    BinGz,
+    PrettyJson,
+    JsonGz,
+}
+
+impl RecorderKind {
+    fn name(&self) -> &'static str {
+        match self {
+            RecorderKind::NamedMpk => "mpk",
+            RecorderKind::NamedMpkGz => "mpk_gz",
+            RecorderKind::Bin => "bin",
+            RecorderKind::BinGz => "bin_gz",
+            RecorderKind::PrettyJson => "json",
+            RecorderKind::JsonGz => "json_gz",
+        }
+    }
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum Precision {
+    Full,
+    Half,
+}
+
+#[derive(Clone, Copy)]
+enum RecordOperation {
+    /// Save the model to the file.
+    Save,
+    /// Load the file into an initialized model.
+    Load,
+    /// Initialize the model, load the file and run the first inference.
+    Startup,
+}
+
+/// Execute the operation on the record of the config.
+pub struct RecordBench(RecordOperation);
+
+impl<B: Backend> Bench<B> for RecordBench {
+    type Config = RecordConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        match config.precision {
+            Precision::Full => {
+                prepare_precision::<B, FullPrecisionSettings>(self.0, config, device)
+            }
+            Precision::Half => {
+                prepare_precision::<B, HalfPrecisionSettings>(self.0, config, device)
+            }
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let precision = match config.precision {
+            Precision::Full => 4,
+            Precision::Half => 2,
+        };
+
+        match self.0 {
+            RecordOperation::Save | RecordOperation::Load => {
+                Some(Work::Bytes((config.num_params() * precision) as u64))
+            }
+            RecordOperation::Startup => None,
+        }
+    }
+}
+
+fn prepare_precision<B: Backend, S: PrecisionSettings>(
+    operation: RecordOperation,
+    config: &RecordConfig,
+    device: &B::Device,
+) -> BenchFunc<B> {
+    match config.recorder {
+        RecorderKind::NamedMpk => {
+            prepare_recorder(operation, config, NamedMpkFileRecorder::<S>::new(), device)
+        }
+        RecorderKind::NamedMpkGz => prepare_recorder(
+            operation,
+            config,
+            NamedMpkGzFileRecorder::<S>::new(),
+            device,
+        ),
+        RecorderKind::Bin => {
+            prepare_recorder(operation, config, BinFileRecorder::<S>::new(), device)
+        }
+        RecorderKind::BinGz => {
+            prepare_recorder(operation, config, BinGzFileRecorder::<S>::new(), device)
+        }
+        RecorderKind::PrettyJson => prepare_recorder(
+            operation,
+            config,
+            PrettyJsonFileRecorder::<S>::new(),
+            device,
+        ),
+        RecorderKind::JsonGz => {
+            prepare_recorder(operation, config, JsonGzFileRecorder::<S>::new(), device)
+        }
+    }
+}
+
+fn prepare_recorder<B: Backend, FR: FileRecorder + 'static>(
+    operation: RecordOperation,
+    config: &RecordConfig,
+    recorder: FR,
+    device: &B::Device,
+) -> BenchFunc<B> {
+    // The record is named after the case, the recorder adding the extension of its format, and
+    // is deleted with its directory when the benchmark drops its function.
+    std::fs::create_dir_all(RECORDS_DIR).expect("The records directory should be created");
+    let dir = tempfile::Builder::new()
+        .prefix("burn-benches-")
+        .tempdir_in(RECORDS_DIR)
+        .expect("The temporary directory should be created");
+    let name = BenchCase::identified(&RecordBenchSuite::label(config), config.clone()).id;
+
+    match config.model {
+        RecordedModel::Mlp => {
+            let mlp = MlpConfig::new(1, config.num_layers, config.d_model);
+            let input = Tensor::<B, 2>::ones([1, config.d_model]).to_device(device);
+
+            prepare_operation(
+                operation,
+                dir,
+                name,
+                recorder,
+                move || Mlp::new(&mlp),
+                move |model: &Mlp<B>| model.forward(input.clone()).flatten(0, 1),
+                device,
+            )
+        }
+        RecordedModel::Transformer => {
+            let encoder = TransformerEncoderConfig::new(
+                config.d_model,
+                4 * config.d_model,
+                8,
+                config.num_layers,
+            );
+            let input = Tensor::<B, 3>::ones([1, SEQ_LENGTH, config.d_model]).to_device(device);
+
+            prepare_operation(
+                operation,
+                dir,
+                name,
+                recorder,
+                move || encoder.init(),
+                move |model: &TransformerEncoder<B>| {
+                    model
+                        .forward(TransformerEncoderInput::new(input.clone()))
+                        .flatten(0, 2)
+                },
+                device,
+            )
+        }
+    }
+}
+
+fn prepare_operation<B, M, FR, I, F>(
+    operation: RecordOperation,
+    dir: TempDir,
+    name: String,
+    recorder: FR,
+    init: I,
+    forward: F,
+    device: &B::Device,
+) -> BenchFunc<B>
+where
+    B: Backend,
+    M: Module<B> + 'static,
+    FR: FileRecorder + 'static,
+    I: Fn() -> M + 'static,
+    F: Fn(&M) -> Tensor<B, 1> + 'static,
+{
+    // Owns the directory, so it lives as long as the function.
+    let path = move || dir.path().join(&name);
+    let model = init().to_device(device);
+    model
+        .clone()
+        .save_file(path(), &recorder)
+        .expect("The record should be saved");
+    let output = Tensor::<B, 1>::zeros([1]).to_device(device);
+    let device = device.clone();
+
+    match operation {
+        RecordOperation::Save => Box::new(move || {
+            model
+                .clone()
+                .save_file(path(), &recorder)
+                .expect("The record should be saved");
+            output.clone()
+        }),
+        RecordOperation::Load => Box::new(move || {
+            model
+                .clone()
+                .load_file(path(), &recorder)
+                .expect("The record should be loaded")
+                .to_device(&device);
+            output.clone()
+        }),
+        RecordOperation::Startup => Box::new(move || {
+            let model = init()
+                .load_file(path(), &recorder)
+                .expect("The record should be loaded")
+                .to_device(&device);
+            forward(&model)
+        }),
+    }
+}
@@ -0,0 +1,126 @@
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use burn::{
+    config::Config,
+    module::Module,
+    nn::gru::GruConfig,
+    nn::lstm::LstmConfig,
+    tensor::{backend::Backend, Distribution, Tensor},
+};
+use criterion::Criterion;
+
+pub struct RecurrentBenchSuite;
+
+impl BenchSuite for RecurrentBenchSuite {
+    type Config = RecurrentConfig;
+
+    fn name() -> String {
+        "recurrent".into()
+    }
+
+    fn description() -> String {
+        "Recurrent layers (LSTM and GRU) benchmarks.".into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let cell = match config.cell {
+            RecurrentCell::Lstm => "lstm",
+            RecurrentCell::Gru => "gru",
+        };
+
+        format!(
+            "{cell}-b{}-s{}-i{}-h{}",
+            config.batch_size, config.seq_length, config.d_input, config.d_hidden
+        )
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        [RecurrentCell::Lstm, RecurrentCell::Gru]
+            .into_iter()
+            .flat_map(|cell| {
+                [
+                    RecurrentConfig::new(cell.clone(), 1, 128, 64, 128),
+                    RecurrentConfig::new(cell.clone(), 32, 128, 128, 256),
+                    RecurrentConfig::new(cell.clone(), 32, 512, 128, 256),
+                    RecurrentConfig::new(cell, 32, 128, 512, 1024),
+                ]
+            })
+            .collect()
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(
+            c,
+            &Self::name(),
+            &Self::configs(),
+            &RecurrentBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct RecurrentConfig {
+    pub cell: RecurrentCell,
+    pub batch_size: usize,
+    pub seq_length: usize,
+    pub d_input: usize,
+    pub d_hidden: usize,
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum RecurrentCell {
+    Lstm,
+    Gru,
+}
+
+impl RecurrentCell {
+    /// Number of gates, each one projecting the input and the hidden state.
+    fn gates(&self) -> usize {
+        match self {
+            RecurrentCell::Lstm => 4,
+            RecurrentCell::Gru => 3,
+        }
+    }
+}
+
+pub struct RecurrentBench;
+
+impl<B: Backend> Bench<B> for RecurrentBench {
+    type Config = RecurrentConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let tensor = Tensor::<B, 3>::random(
+            [config.batch_size, config.seq_length, config.d_input],
+            Distribution::Default,
+        )
+        .to_device(device);
+
+        match config.cell {
+            RecurrentCell::Lstm => {
+                let lstm = LstmConfig::new(config.d_input, config.d_hidden, true)
+                    .init()
+                    .to_device(device);
+
+                Box::new(move || {
+                    let (_cell_states, hidden_states) = lstm.forward(tensor.clone(), None);
+                    hidden_states.flatten(0, 2)
+                })
+            }
+            RecurrentCell::Gru => {
+                let gru = GruConfig::new(config.d_input, config.d_hidden, true)
+                    .init()
+                    .to_device(device);
+
+                Box::new(move || gru.forward(tensor.clone(), None).flatten(0, 2))
+            }
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let step = 2 * config.batch_size * config.d_hidden * (config.d_input + config.d_hidden);
+
+        Some(Work::Flops(
+            (config.seq_length * config.cell.gates() * step) as u64,
+        ))
+    }
+}
@@ -0,0 +1,170 @@
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
+use burn::{
+    config::Config,
+    tensor::{activation, backend::Backend, Distribution, Tensor},
+};
+use criterion::Criterion;
+
+pub struct ReductionBenchSuite;
+
+impl BenchSuite for ReductionBenchSuite {
+    type Config = ReductionConfig;
+
+    fn name() -> String {
+        "reduction".into()
+    }
+
+    fn description() -> String {
+        "Reduction benchmarks along a contiguous or strided dimension. The argmax has no \
+         gradient, so it has no autodiff variant."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let shape: Vec<_> = config.shape.iter().map(usize::to_string).collect();
+
+        format!("{}-d{}-{}", config.op.name(), config.dim, shape.join("x"))
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        match config.shape.len() {
+            1..=4 if config.dim < config.shape.len() => Ok(()),
+            1..=4 => Err(format!(
+                "the reduced dimension {} doesn't exist",
+                config.dim
+            )),
+            _ => Err("tensors with 1 to 4 dimensions are supported".into()),
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        let ops = [
+            ReductionOp::Sum,
+            ReductionOp::Mean,
+            ReductionOp::Max,
+            ReductionOp::Argmax,
+            ReductionOp::Softmax,
+            ReductionOp::LogSoftmax,
+        ];
+        let mut configs: Vec<_> = ops
+            .into_iter()
+            .flat_map(|op| {
+                // Contiguous and strided dimensions.
+                [1, 0].map(|dim| ReductionConfig::new(op.clone(), vec![1024, 1024], dim))
+            })
+            .collect();
+
+        // Attention scores.
+        configs.push(ReductionConfig::new(
+            ReductionOp::Softmax,
+            vec![8, 12, 512, 512],
+            3,
+        ));
+
+        configs
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(
+            c,
+            &Self::name(),
+            &Self::configs(),
+            &ReductionBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct ReductionConfig {
+    pub op: ReductionOp,
+    /// Shape of the tensor, with 1 to 4 dimensions.
+    pub shape: Vec<usize>,
+    /// Dimension reduced, the last one being contiguous.
+    pub dim: usize,
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum ReductionOp {
+    Sum,
+    Mean,
+    Max,
+    Argmax,
+    Softmax,
+    LogSoftmax,
+}
+
+impl ReductionOp {
+    fn name(&self) -> &'static str {
+        match self {
+            ReductionOp::Sum => "sum",
+            ReductionOp::Mean => "mean",
+            ReductionOp::Max => "max",
+            ReductionOp::Argmax => "argmax",
+            ReductionOp::Softmax => "softmax",
+            ReductionOp::LogSoftmax => "log_softmax",
+        }
+    }
+
+    fn apply<B: Backend, const D: usize>(&self, tensor: Tensor<B, D>, dim: usize) -> Tensor<B, D> {
+        match self {
+            ReductionOp::Sum => tensor.sum_dim(dim),
+            ReductionOp::Mean => tensor.mean_dim(dim),
+            ReductionOp::Max => tensor.max_dim(dim),
+            ReductionOp::Argmax => tensor.argmax(dim).float(),
+            ReductionOp::Softmax => activation::softmax(tensor, dim),
+            ReductionOp::LogSoftmax => activation::log_softmax(tensor, dim),
+        }
+    }
+
+    /// Whether the output has the shape of the input instead of a reduced dimension.
+    fn is_normalization(&self) -> bool {
+        matches!(self, ReductionOp::Softmax | ReductionOp::LogSoftmax)
+    }
+}
+
+pub struct ReductionBench;
+
+impl<B: Backend> Bench<B> for ReductionBench {
+    type Config = ReductionConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        match config.shape.len() {
+            1 => prepare::<B, 1>(config, device),
+            2 => prepare::<B, 2>(config, device),
+            3 => prepare::<B, 3>(config, device),
+            4 => prepare::<B, 4>(config, device),
+            _ => unreachable!("The rank is validated by the suite"),
+        }
+    }
+
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let elements: usize = config.shape.iter().product();
+        let outputs = match config.op.is_normalization() {
+            true => elements,
+            false => elements / config.shape[config.dim],
+        };
+        // The input is read and the output written.
+        let bytes = (elements + outputs) * std::mem::size_of::<B::FloatElem>();
+
+        Some(Work::Bytes(bytes as u64))
+    }
+
+    fn differentiable(&self, config: &Self::Config) -> bool {
+        config.op != ReductionOp::Argmax
+    }
+}
+
+fn prepare<B: Backend, const D: usize>(
+    config: &ReductionConfig,
+    device: &B::Device,
+) -> BenchFunc<B> {
+    let mut shape = [0; D];
+    shape.copy_from_slice(&config.shape);
+    let tensor = Tensor::<B, D>::random(shape, Distribution::Default)
+        .to_device(device)
+        .require_grad();
+    let (op, dim) = (config.op.clone(), config.dim);
+
+    Box::new(move || op.apply(tensor.clone(), dim).flatten(0, D - 1))
+}
@@ -0,0 +1,236 @@
+use crate::bench::{run_benchmark, training_step, Bench, BenchFunc, BenchSuite, Work};
+use crate::{conv2d, mlp, transformer};
+use burn::{
+    backend::Autodiff,
+    config::Config,
+    module::{AutodiffModule, Module},
+    nn::conv::Conv2dConfig,
+    nn::transformer::{TransformerEncoder, TransformerEncoderConfig, TransformerEncoderInput},
+    nn::PaddingConfig2d,
+    optim::{AdamConfig, AdamWConfig, SgdConfig},
+    tensor::{
+        backend::{AutodiffBackend, Backend},
+        Distribution, Tensor,
+    },
+};
+use criterion::Criterion;
+
+pub struct TrainingBenchSuite;
+
+impl BenchSuite for TrainingBenchSuite {
+    type Config = TrainingConfig;
+
+    fn name() -> String {
+        "training".into()
+    }
+
+    fn description() -> String {
+        "Training step benchmarks on the autodiff backend, executing the forward pass, the mean \
+         squared error loss, the backward pass and the update of the optimizer."
+            .into()
+    }
+
+    fn label(config: &Self::Config) -> String {
+        let model = match &config.model {
+            TrainedModel::Mlp(mlp) => format!("mlp-{}", mlp::MlpBenchSuite::label(mlp)),
+            TrainedModel::Conv2d(conv2d) => {
+                format!("conv2d-{}", conv2d::Conv2dBenchSuite::label(conv2d))
+            }
+            TrainedModel::Transformer(transformer) => format!(
+                "transformer-{}",
+                transformer::TransformerBenchSuite::label(transformer)
+            ),
+        };
+
+        format!("{model}-{}", config.optimizer.name())
+    }
+
+    fn element() -> Option<&'static str> {
+        Some("step")
+    }
+
+    fn validate(config: &Self::Config) -> Result<(), String> {
+        match &config.model {
+            TrainedModel::Mlp(mlp) => mlp::MlpBenchSuite::validate(mlp),
+            TrainedModel::Conv2d(conv2d) => conv2d::Conv2dBenchSuite::validate(conv2d),
+            TrainedModel::Transformer(transformer) => {
+                transformer::TransformerBenchSuite::validate(transformer)
+            }
+        }
+    }
+
+    fn default_configs() -> Vec<Self::Config> {
+        [
+            TrainedModel::Mlp(mlp::MlpConfig::new(32, 4, 1024)),
+            TrainedModel::Conv2d(conv2d::Conv2dBenchConfig::new(
+                8,
+                56,
+                56,
+                2,
+                Conv2dConfig::new([64, 64], [3, 3]).with_padding(PaddingConfig2d::Same),
+            )),
+            TrainedModel::Transformer(transformer::TransformerConfig::new(
+                4,
+                128,
+                TransformerEncoderConfig::new(256, 1024, 8, 4).with_dropout(0.0),
+            )),
+        ]
+        .into_iter()
+        .flat_map(|model| {
+            [
+                OptimizerKind::Sgd,
+                OptimizerKind::Adam,
+                OptimizerKind::AdamW,
+            ]
+            .map(|optimizer| TrainingConfig::new(model.clone(), optimizer))
+        })
+        .collect()
+    }
+
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark::<Autodiff<B>, _, _>(
+            c,
+            &Self::name(),
+            &Self::configs(),
+            &TrainingBench,
+            device,
+        );
+    }
+}
+
+#[derive(Config)]
+pub struct TrainingConfig {
+    pub model: TrainedModel,
+    pub optimizer: OptimizerKind,
+    #[config(default = 1e-3)]
+    pub learning_rate: f64,
+}
+
+/// The trained model, configured as in its own suite.
+#[derive(Config)]
+pub enum TrainedModel {
+    /// The [MLP](mlp::Mlp).
+    Mlp(mlp::MlpConfig),
+    /// The [Conv2d block](conv2d::Conv2dBlock).
+    Conv2d(conv2d::Conv2dBenchConfig),
+    /// The transformer encoder.
+    Transformer(transformer::TransformerConfig),
+}
+
+#[derive(Config, Debug, PartialEq)]
+pub enum OptimizerKind {
+    Sgd,
+    Adam,
+    AdamW,
+}
+
+impl OptimizerKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OptimizerKind::Sgd => "sgd",
+            OptimizerKind::Adam => "adam",
+            OptimizerKind::AdamW => "adamw",
+        }
+    }
+}
+
+pub struct TrainingBench;
+
+impl<B: AutodiffBackend> Bench<B> for TrainingBench {
+    type Config = TrainingConfig;
+
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let (optimizer, learning_rate) = (&config.optimizer, config.learning_rate);
+
+        match &config.model {
+            TrainedModel::Mlp(mlp) => {
+                let model = mlp::Mlp::new(mlp).to_device(device);
+                let input = random([mlp.batch_size, mlp.d_model], device);
+
+                train(
+                    optimizer,
+                    learning_rate,
+                    model,
+                    move |model: &mlp::Mlp<B>| model.forward(input.clone()),
+                )
+            }
+            TrainedModel::Conv2d(conv2d) => {
+                let model = conv2d::Conv2dBlock::new(conv2d).to_device(device);
+                let shape = [
+                    conv2d.batch_size,
+                    conv2d.conv2d.channels[0],
+                    conv2d.height,
+                    conv2d.width,
+                ];
+                let input = random(shape, device);
+
+                train(
+                    optimizer,
+                    learning_rate,
+                    model,
+                    move |model: &conv2d::Conv2dBlock<B>| model.forward(input.clone()),
+                )
+            }
+            TrainedModel::Transformer(transformer) => {
+                let model = transformer.encoder.init().to_device(device);
+                let shape = [
+                    transformer.batch_size,
+                    transformer.seq_length,
+                    transformer.encoder.d_model,
+                ];
+                let input = random(shape, device);
+
+                train(
+                    optimizer,
+                    learning_rate,
+                    model,
+                    move |model: &TransformerEncoder<B>| {
+                        model.forward(TransformerEncoderInput::new(input.clone()))
+                    },
+                )
+            }
+        }
+    }
+
+    fn work(&self, _config: &Self::Config) -> Option<Work> {
+        Some(Work::Elements(1))
+    }
+}
+
+fn random<B: Backend, const D: usize>(shape: [usize; D], device: &B::Device) -> Tensor<B, D> {
+    Tensor::random(shape, Distribution::Default).to_device(device)
+}
+
+/// Train the model with the optimizer.
+fn train<B, M, F, const D: usize>(
+    optimizer: &OptimizerKind,
+    learning_rate: f64,
+    model: M,
+    forward: F,
+) -> BenchFunc<B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B> + 'static,
+    F: Fn(&M) -> Tensor<B, D> + 'static,
+{
+    match optimizer {
+        OptimizerKind::Sgd => training_step(
+            model,
+            SgdConfig::new().init::<B, M>(),
+            learning_rate,
+            forward,
+        ),
+        OptimizerKind::Adam => training_step(
+            model,
+            AdamConfig::new().init::<B, M>(),
+            learning_rate,
+            forward,
+        ),
+        OptimizerKind::AdamW => training_step(
+            model,
+            AdamWConfig::new().init::<B, M>(),
+            learning_rate,
+            forward,
+        ),
+    }
+}
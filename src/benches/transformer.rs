@@ -1,8 +1,4 @@
-use crate::{
-    bench::{run_benchmark, Bench, BenchFunc, BenchSuite},
-    device, BenchBackend,
-};
-use burn::backend::Autodiff;
+use crate::bench::{run_benchmark_variants, Bench, BenchFunc, BenchSuite, Work};
 use burn::{
     config::Config,
     module::Module,
@@ -14,99 +10,91 @@ use criterion::Criterion;
 pub struct TransformerBenchSuite;
 
 impl BenchSuite for TransformerBenchSuite {
+    type Config = TransformerConfig;
+
     fn name() -> String {
         "transformer".into()
     }
 
-    fn details() -> String {
-        let mut details = String::from("Transformer encoder benchmarks.\n\n");
-
-        configs()
-            .into_iter()
-            .enumerate()
-            .for_each(|(i, config)| details += format!("- {} => `{}`\n", i + 1, config).as_str());
-
-        details
+    fn description() -> String {
+        "Transformer encoder benchmarks.".into()
     }
 
-    fn run(c: &mut Criterion) {
-        let name = Self::name();
-        let name_autodiff = format!("{}-autodiff", Self::name());
+    fn label(config: &Self::Config) -> String {
+        format!(
+            "b{}-s{}-d{}-ff{}-h{}-l{}",
+            config.batch_size,
+            config.seq_length,
+            config.encoder.d_model,
+            config.encoder.d_ff,
+            config.encoder.n_heads,
+            config.encoder.n_layers,
+        )
+    }
 
-        run_benchmark(c, &name, configs(), TansformerBench::new());
-        run_benchmark(c, &name_autodiff, configs(), TansformerBenchAD::new());
+    fn default_configs() -> Vec<Self::Config> {
+        vec![
+            TransformerConfig::new(
+                4,
+                128,
+                TransformerEncoderConfig::new(64, 128, 4, 4).with_dropout(0.0),
+            ),
+            TransformerConfig::new(
+                4,
+                128,
+                TransformerEncoderConfig::new(64, 256, 4, 4).with_dropout(0.0),
+            ),
+            TransformerConfig::new(
+                4,
+                128,
+                TransformerEncoderConfig::new(256, 1024, 8, 4).with_dropout(0.0),
+            ),
+        ]
     }
-}
 
-fn configs() -> Vec<TransformerConfig> {
-    vec![
-        TransformerConfig::new(
-            4,
-            128,
-            TransformerEncoderConfig::new(64, 128, 4, 4).with_dropout(0.0),
-        ),
-        TransformerConfig::new(
-            4,
-            128,
-            TransformerEncoderConfig::new(64, 256, 4, 4).with_dropout(0.0),
-        ),
-        TransformerConfig::new(
-            4,
-            128,
-            TransformerEncoderConfig::new(256, 1024, 8, 4).with_dropout(0.0),
-        ),
-    ]
+    fn run_backend<B: Backend>(c: &mut Criterion, device: &B::Device) {
+        run_benchmark_variants::<B, _, _>(
+            c,
+            &Self::name(),
+            &Self::configs(),
+            &TansformerBench,
+            device,
+        );
+    }
 }
 
-#[derive(new)]
 pub struct TansformerBench;
-#[derive(new)]
-pub struct TansformerBenchAD;
 
-impl Bench for TansformerBench {
+impl<B: Backend> Bench<B> for TansformerBench {
     type Config = TransformerConfig;
 
-    fn prepare(&self, config: &Self::Config) -> BenchFunc {
-        let device = device();
-        let tensor = Tensor::<BenchBackend, 3>::ones([
-            config.batch_size,
-            config.seq_length,
-            config.encoder.d_model,
-        ])
-        .to_device(&device);
-        let transformer = config.encoder.init().to_device(&device);
+    fn prepare(&self, config: &Self::Config, device: &B::Device) -> BenchFunc<B> {
+        let tensor =
+            Tensor::<B, 3>::ones([config.batch_size, config.seq_length, config.encoder.d_model])
+                .to_device(device);
+        let transformer = config.encoder.init().to_device(device);
 
         Box::new(move || {
             let input = TransformerEncoderInput::new(tensor.clone());
-            let tensor = transformer.forward(input);
-            <BenchBackend as Backend>::sync(&device);
+            transformer.forward(input).flatten(0, 2)
         })
     }
-}
-
-impl Bench for TansformerBenchAD {
-    type Config = TransformerConfig;
 
-    fn prepare(&self, config: &Self::Config) -> BenchFunc {
-        type ADBackend = Autodiff<BenchBackend>;
-
-        let device = device();
-        let tensor = Tensor::<ADBackend, 3>::ones([
-            config.batch_size,
-            config.seq_length,
-            config.encoder.d_model,
-        ])
-        .to_device(&device);
-        let transformer = config.encoder.init().to_device(&device);
-
-        Box::new(move || {
-            let input = TransformerEncoderInput::new(tensor.clone());
-            let tensor = transformer.forward(input);
-            let _grads = tensor.backward();
-            <BenchBackend as Backend>::sync(&device);
-        })
+    fn work(&self, config: &Self::Config) -> Option<Work> {
+        let encoder = &config.encoder;
+        let tokens = config.batch_size * config.seq_length;
+        // Query, key, value and output projections.
+        let projections = 4 * 2 * tokens * encoder.d_model * encoder.d_model;
+        // Attention scores and their weighted sum of the values.
+        let attention = 2 * 2 * tokens * config.seq_length * encoder.d_model;
+        let feed_forward = 2 * 2 * tokens * encoder.d_model * encoder.d_ff;
+
+        Some(Work::Flops(
+            (encoder.n_layers * (projections + attention + feed_forward)) as u64,
+        ))
     }
 }
+
 #[derive(Config)]
 pub struct TransformerConfig {
     pub batch_size: usize,
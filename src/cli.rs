@@ -1,17 +1,41 @@
-use crate::tables::make_tables;
+use crate::backend::BACKEND_ENV;
+use crate::compare::{compare, CompareOptions, Verdict};
+use crate::config_file::{ConfigFile, ConfigFileError, CONFIG_FILE_ENV};
+use crate::history::{self, History, HistoryEntry};
+use crate::report::{self, ReportOptions};
+use crate::results::{self, BenchResult, ResultsError};
+use crate::tables::{self, machine_fingerprint};
 use crate::version_file;
-use clap::{Parser, ValueEnum};
+use crate::workspace::{BurnSource, Workspace};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::fs::File;
-use std::io::Write;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::str::FromStr;
 
-static SH_FILENAME: &str = "target/tmp/run_burn.sh";
 static OUTPUT_DIR: &str = "target/burn_benches";
 static MD_FILENAME: &str = "target/burn_benches/BENCHMARKS.md";
 static HTML_FILENAME: &str = "target/burn_benches/benchmarks.html";
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+pub struct BenchesCli {
+    #[command(subcommand)]
+    pub command: Option<BenchesCommand>,
+    #[command(flatten)]
+    pub args: BenchesArgs,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BenchesCommand {
+    /// Inspect and manage the history of the benchmark results.
+    #[command(subcommand)]
+    History(HistoryCommand),
+    /// Detect the regressions of a burn revision compared to another one.
+    Compare(CompareArgs),
+}
+
+#[derive(Args, Debug)]
 pub struct BenchesArgs {
     #[arg(short('B'), long, num_args(1..))]
     backends: Vec<Backend>,
@@ -23,10 +47,91 @@ pub struct BenchesArgs {
     branches: Vec<String>,
     #[arg(short, long, num_args(0..))]
     paths: Vec<String>,
-    #[arg(short('N'), long)]
+    #[arg(short('N'), long, value_enum, default_value_t = Bench::All)]
+    bench: Bench,
+    #[arg(short, long, default_value_t = String::from("https://github.com/burn-rs/burn/"))]
+    repository: String,
+    /// Column used as the reference for the speedups, formatted as `backend:version`.
+    #[arg(long)]
+    baseline: Option<String>,
+    /// Peak compute of the machine in GFLOP/s, to report the achieved fraction of it.
+    #[arg(long)]
+    peak_gflops: Option<f64>,
+    /// Peak memory bandwidth of the machine in GB/s, to report the achieved fraction of it.
+    #[arg(long)]
+    peak_gbps: Option<f64>,
+    /// TOML or JSON file listing the configs of each suite, replacing the built-in ones.
+    #[arg(long)]
+    config_file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    #[arg(short('B'), long, num_args(1..), required = true)]
+    backends: Vec<Backend>,
+    /// Reference burn source, formatted as `tag:<tag>`, `rev:<commit>`, `branch:<branch>` or
+    /// `path:<path>`.
+    #[arg(long)]
+    baseline: BurnRef,
+    /// Burn source checked for regressions, formatted like the baseline.
+    #[arg(long)]
+    candidate: BurnRef,
+    #[arg(short('N'), long, value_enum, default_value_t = Bench::All)]
     bench: Bench,
     #[arg(short, long, default_value_t = String::from("https://github.com/burn-rs/burn/"))]
-    pub repository: String,
+    repository: String,
+    /// Minimum change of the mean time, in percent, to consider a benchmark improved or regressed.
+    #[arg(long, default_value_t = 5.0)]
+    threshold: f64,
+    /// Significance level of the statistical test.
+    #[arg(long, default_value_t = 0.05)]
+    significance: f64,
+    /// TOML or JSON file listing the configs of each suite, replacing the built-in ones.
+    #[arg(long)]
+    config_file: Option<PathBuf>,
+}
+
+/// A burn source, given on the command line as `<tag|rev|branch|path>:<value>`.
+#[derive(new, Debug, Clone)]
+pub struct BurnRef {
+    identifier: String,
+    value: String,
+}
+
+impl FromStr for BurnRef {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(':') {
+            Some((identifier, value)) if ["tag", "rev", "branch", "path"].contains(&identifier) => {
+                Ok(Self::new(identifier.into(), value.into()))
+            }
+            _ => Err(format!(
+                "invalid burn source '{value}', expected tag:<tag>, rev:<commit>, branch:<branch> or path:<path>"
+            )),
+        }
+    }
+}
+
+impl BurnRef {
+    fn version(&self) -> String {
+        self.value.replace('/', "-")
+    }
+
+    fn param(&self, backend: &Backend, features: String, bench: &Bench) -> BenchParam {
+        let settings = BenchSettings {
+            identifier: self.identifier.clone(),
+            value: self.value.clone(),
+            backend_flag: backend.flag().into(),
+            features,
+            bench: bench.name(),
+        };
+
+        match self.identifier.as_str() {
+            "path" => BenchParam::Path(settings),
+            _ => BenchParam::Git(settings),
+        }
+    }
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -45,9 +150,99 @@ pub enum Bench {
     Transformer,
     MLP,
     Conv2d,
+    Matmul,
+    Attention,
+    Decoder,
+    Recurrent,
+    Norm,
+    Elementwise,
+    Reduction,
+    DataMovement,
+    Conv1d,
+    ConvTranspose2d,
+    Pool2d,
+    Models,
+    Training,
+    Optimizer,
+    Record,
     All,
 }
 
+impl Backend {
+    fn flag(&self) -> &'static str {
+        match self {
+            Backend::Ndarray => "ndarray",
+            Backend::NdarrayNetlib => "ndarray-blas-netlib",
+            Backend::NdarrayOpenblas => "ndarray-blas-openblas",
+            Backend::NdarrayNoStd => "ndarray-no-std",
+            Backend::TchCpu => "tch-cpu",
+            Backend::TchGpu => "tch-gpu",
+            Backend::Wgpu => "wgpu",
+        }
+    }
+}
+
+impl Bench {
+    /// Name of the cargo bench target, none when all the benches are executed.
+    fn name(&self) -> Option<String> {
+        match self {
+            Bench::Transformer => Some("transformer"),
+            Bench::MLP => Some("mlp"),
+            Bench::Conv2d => Some("conv2d"),
+            Bench::Matmul => Some("matmul"),
+            Bench::Attention => Some("attention"),
+            Bench::Decoder => Some("decoder"),
+            Bench::Recurrent => Some("recurrent"),
+            Bench::Norm => Some("norm"),
+            Bench::Elementwise => Some("elementwise"),
+            Bench::Reduction => Some("reduction"),
+            Bench::DataMovement => Some("data_movement"),
+            Bench::Conv1d => Some("conv1d"),
+            Bench::ConvTranspose2d => Some("conv_transpose2d"),
+            Bench::Pool2d => Some("pool2d"),
+            Bench::Models => Some("models"),
+            Bench::Training => Some("training"),
+            Bench::Optimizer => Some("optimizer"),
+            Bench::Record => Some("record"),
+            Bench::All => None,
+        }
+        .map(String::from)
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HistoryCommand {
+    /// List the recorded runs.
+    List,
+    /// Show the recorded results matching the filters.
+    Query(HistoryFilter),
+    /// Remove the recorded results matching the filters.
+    Prune {
+        #[command(flatten)]
+        filter: HistoryFilter,
+        /// Only remove the results recorded more than this number of days ago.
+        #[arg(long)]
+        older_than: Option<u64>,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct HistoryFilter {
+    #[arg(long)]
+    suite: Option<String>,
+    #[arg(long)]
+    group: Option<String>,
+    #[arg(long)]
+    config: Option<String>,
+    #[arg(long)]
+    backend: Option<String>,
+    /// Prefix of the burn commit.
+    #[arg(long)]
+    revision: Option<String>,
+    #[arg(long)]
+    machine: Option<String>,
+}
+
 #[derive(Clone)]
 pub enum BenchParam {
     Path(BenchSettings),
@@ -56,14 +251,40 @@ pub enum BenchParam {
 
 impl BenchSettings {
     fn bench_filename(&self) -> String {
-        let value = self.value.replace('/', "-");
-
         format!(
             "{}/{}-{}-{}.json",
-            OUTPUT_DIR, self.backend_flag, self.identifier, value
+            OUTPUT_DIR,
+            self.backend_flag,
+            self.identifier,
+            self.version()
         )
     }
+
+    fn version(&self) -> String {
+        self.value.replace('/', "-")
+    }
+
+    /// The workspace of the burn source, shared by all the backends.
+    fn workspace(&self) -> Workspace {
+        Workspace::new(&format!("{}-{}", self.identifier, self.version()))
+    }
+
+    fn cargo_features(&self) -> [String; 3] {
+        [
+            "--no-default-features".into(),
+            "--features".into(),
+            self.features.clone(),
+        ]
+    }
+
+    fn cargo_bench(&self) -> Vec<String> {
+        match &self.bench {
+            Some(bench) => vec!["--bench".into(), bench.clone()],
+            None => Vec::new(),
+        }
+    }
 }
+
 impl BenchParam {
     pub fn settings(&self) -> &BenchSettings {
         match self {
@@ -71,6 +292,101 @@ impl BenchParam {
             BenchParam::Git(val) => val,
         }
     }
+
+    fn source<'a>(&'a self, repo: &'a str) -> BurnSource<'a> {
+        match self {
+            BenchParam::Path(settings) => BurnSource::Path(&settings.value),
+            BenchParam::Git(settings) => BurnSource::Git {
+                repo,
+                reference: &settings.identifier,
+                value: &settings.value,
+            },
+        }
+    }
+
+    /// The ordered steps required to benchmark this parameter.
+    ///
+    /// The workspace is only created once per burn source, so all the backends are benchmarked
+    /// against the same commit.
+    fn steps(&self, create_workspace: bool) -> Vec<ParamStep> {
+        let mut steps = Vec::new();
+
+        if create_workspace {
+            steps.push(ParamStep::CreateWorkspace);
+        }
+
+        steps.extend([ParamStep::WriteVersion, ParamStep::Build, ParamStep::Run]);
+        steps
+    }
+
+    fn execute(&self, step: &ParamStep, repo: &str) -> Result<(), BenchError> {
+        let settings = self.settings();
+        let workspace = settings.workspace();
+        let stage = &Step::Param(step.clone());
+
+        match step {
+            ParamStep::CreateWorkspace => workspace
+                .create(&self.source(repo))
+                .map_err(|err| BenchError::io(stage, err)),
+            ParamStep::WriteVersion => {
+                std::fs::write(workspace.dir().join(version_file()), settings.version())
+                    .map_err(|err| BenchError::io(stage, err))
+            }
+            ParamStep::Build => run_command(
+                stage,
+                Command::new("cargo")
+                    .current_dir(workspace.dir())
+                    .args(["bench", "--no-run"])
+                    .args(settings.cargo_features())
+                    .args(settings.cargo_bench()),
+            ),
+            ParamStep::Run => {
+                let output = File::create(settings.bench_filename())
+                    .map_err(|err| BenchError::io(stage, err))?;
+
+                run_command(
+                    stage,
+                    Command::new("cargo")
+                        .current_dir(workspace.dir())
+                        .arg("criterion")
+                        .args(settings.cargo_features())
+                        .args(settings.cargo_bench())
+                        .arg("--message-format=json")
+                        .env(BACKEND_ENV, &settings.backend_flag)
+                        .stdout(output),
+                )
+            }
+        }
+    }
+
+    /// Append the results to the [history](History), along with the resolved burn commit.
+    fn record(&self, repo: &str, machine: &str, results: &[BenchResult]) -> Result<(), BenchError> {
+        let settings = self.settings();
+        let revision = settings
+            .workspace()
+            .revision(&self.source(repo))
+            .unwrap_or_else(|| settings.value.clone());
+        let timestamp = history::now();
+
+        let entries: Vec<_> = results
+            .iter()
+            .map(|result| {
+                HistoryEntry::new(result.clone(), revision.clone(), machine.into(), timestamp)
+            })
+            .collect();
+
+        History::default()
+            .append(&entries)
+            .map_err(|err| BenchError::io(&Step::Record, err))
+    }
+
+    /// Load the results written by the [run](ParamStep::Run) step.
+    fn results(&self) -> Result<Vec<BenchResult>, BenchError> {
+        results::load(self.settings().bench_filename()).map_err(|error| BenchError::Results {
+            step: Step::Param(ParamStep::Run),
+            error,
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -78,12 +394,127 @@ pub struct BenchSettings {
     identifier: String,
     value: String,
     backend_flag: String,
-    bench: String,
+    features: String,
+    bench: Option<String>,
+}
+
+/// A stage of the benchmark pipeline executed for each parameter.
+#[derive(Debug, Clone)]
+pub enum ParamStep {
+    /// Generate the scratch crate depending on the benchmarked burn source.
+    CreateWorkspace,
+    /// Record the burn version used to label the benchmarks.
+    WriteVersion,
+    /// Compile the benchmarks without running them.
+    Build,
+    /// Run the benchmarks and save criterion's JSON messages.
+    Run,
+}
+
+/// A single stage of the benchmark pipeline.
+#[derive(Debug, Clone)]
+pub enum Step {
+    /// A stage executed for each parameter.
+    Param(ParamStep),
+    /// Append the results of a parameter to the history, once they are loaded.
+    Record,
+    /// Render the report of all the results.
+    Collect,
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Param(ParamStep::CreateWorkspace) => f.write_str("create workspace"),
+            Step::Param(ParamStep::WriteVersion) => f.write_str("write version"),
+            Step::Param(ParamStep::Build) => f.write_str("build"),
+            Step::Param(ParamStep::Run) => f.write_str("run"),
+            Step::Record => f.write_str("record"),
+            Step::Collect => f.write_str("collect"),
+        }
+    }
+}
+
+/// Error returned when a [step](Step) of the pipeline fails.
+#[derive(Debug)]
+pub enum BenchError {
+    /// The step couldn't be started or its files couldn't be written.
+    Io { step: Step, error: std::io::Error },
+    /// The command executed by the step returned a non-zero exit status.
+    Status { step: Step, status: ExitStatus },
+    /// The results written by the step couldn't be parsed.
+    Results { step: Step, error: ResultsError },
+}
+
+impl BenchError {
+    fn io(step: &Step, error: std::io::Error) -> Self {
+        Self::Io {
+            step: step.clone(),
+            error,
+        }
+    }
+}
+
+impl std::fmt::Display for BenchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchError::Io { step, error } => write!(f, "step '{step}' failed: {error}"),
+            BenchError::Status { step, status } => write!(f, "step '{step}' failed: {status}"),
+            BenchError::Results { step, error } => write!(f, "step '{step}' failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for BenchError {}
+
+/// Outcome of every backend/revision combination benchmarked by [Benches::run].
+pub struct Summary {
+    runs: Vec<(BenchSettings, Result<Vec<BenchResult>, BenchError>)>,
+    collect: Result<(), BenchError>,
+}
+
+impl Summary {
+    pub fn is_success(&self) -> bool {
+        self.collect.is_ok() && self.runs.iter().all(|(_, result)| result.is_ok())
+    }
+
+    /// The results of all the successful runs.
+    pub fn results(&self) -> Vec<&BenchResult> {
+        self.runs
+            .iter()
+            .filter_map(|(_, result)| result.as_ref().ok())
+            .flatten()
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Summary:")?;
+
+        for (settings, result) in self.runs.iter() {
+            let name = format!(
+                "{} {} {}",
+                settings.backend_flag, settings.identifier, settings.value
+            );
+
+            match result {
+                Ok(results) => writeln!(f, "  [ok]     {name} ({} benchmarks)", results.len())?,
+                Err(err) => writeln!(f, "  [failed] {name}: {err}")?,
+            }
+        }
+
+        match &self.collect {
+            Ok(_) => writeln!(f, "Report written to {MD_FILENAME}"),
+            Err(err) => writeln!(f, "Report not written: {err}"),
+        }
+    }
 }
 
 pub struct Benches {
     params: Vec<BenchParam>,
     repo: String,
+    report: ReportOptions,
 }
 
 impl Drop for Benches {
@@ -93,184 +524,362 @@ impl Drop for Benches {
 }
 
 impl Benches {
-    pub fn new(params: Vec<BenchParam>, repo: String) -> Self {
-        let params_cloned = params.clone();
-        ctrlc::set_handler(move || {
-            cleanup(&params_cloned);
+    pub fn new(params: Vec<BenchParam>, repo: String, report: ReportOptions) -> Self {
+        Self {
+            params,
+            repo,
+            report,
+        }
+    }
+
+    pub fn run(self) -> Summary {
+        prepare();
+        let machine = machine_fingerprint();
+        let mut workspaces = Vec::new();
+
+        let runs: Vec<_> = self
+            .params
+            .iter()
+            .map(|param| {
+                let workspace = param.settings().workspace().dir().to_path_buf();
+                let create_workspace = !workspaces.contains(&workspace);
+
+                let result = param
+                    .steps(create_workspace)
+                    .iter()
+                    .try_for_each(|step| param.execute(step, &self.repo))
+                    .and_then(|_| param.results())
+                    .inspect(|results| {
+                        // The results are still reported when they can't be recorded.
+                        if let Err(err) = param.record(&self.repo, &machine, results) {
+                            eprintln!("{err}");
+                        }
+                    });
+
+                match &result {
+                    Err(BenchError::Io {
+                        step: Step::Param(ParamStep::CreateWorkspace),
+                        ..
+                    }) => {}
+                    _ => workspaces.push(workspace),
+                }
+
+                if let Err(err) = &result {
+                    eprintln!("{err}");
+                }
+
+                (param.settings().clone(), result)
+            })
+            .collect();
+
+        let results: Vec<_> = runs
+            .iter()
+            .filter_map(|(_, result)| result.as_ref().ok())
+            .flatten()
+            .cloned()
+            .collect();
+        let collect = collect(&results, &self.report);
+
+        Summary { runs, collect }
+    }
+}
+
+impl From<BenchesArgs> for Vec<BenchParam> {
+    fn from(args: BenchesArgs) -> Self {
+        let refs: Vec<_> = [
+            ("tag", args.tags),
+            ("rev", args.commits),
+            ("branch", args.branches),
+            ("path", args.paths),
+        ]
+        .into_iter()
+        .flat_map(|(identifier, values)| {
+            values
+                .into_iter()
+                .map(move |value| BurnRef::new(identifier.into(), value))
         })
-        .unwrap();
+        .collect();
 
-        Self { params, repo }
+        params(&args.backends, &args.bench, &refs)
     }
+}
+
+/// Benchmark every burn source with every backend.
+fn params(backends: &[Backend], bench: &Bench, refs: &[BurnRef]) -> Vec<BenchParam> {
+    backends
+        .iter()
+        .flat_map(|backend| {
+            refs.iter()
+                .map(|burn| burn.param(backend, features(backend, backends), bench))
+        })
+        .collect()
+}
+
+/// The cargo features used to build the benchmarks of the backend.
+///
+/// All the backends are enabled together, so each burn source is only compiled once and the
+/// backend is selected at runtime. The ndarray variants are mutually exclusive, so each of them
+/// gets its own build.
+fn features(backend: &Backend, backends: &[Backend]) -> String {
+    let is_ndarray = |flag: &&str| flag.starts_with("ndarray");
+    let flags: Vec<_> = backends.iter().map(Backend::flag).collect();
+
+    let ndarray = match backend.flag() {
+        flag if is_ndarray(&flag) => Some(flag),
+        _ => flags.iter().copied().find(is_ndarray),
+    };
+
+    ndarray
+        .into_iter()
+        .chain(flags.iter().copied().filter(|flag| !is_ndarray(flag)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl BenchesArgs {
+    /// Benchmark every burn source with every backend, returning whether all the runs succeeded.
+    pub fn run(self) -> bool {
+        if !use_config_file(self.config_file.as_deref()) {
+            return false;
+        }
 
-    pub fn run(self) {
-        prepare(&self.params, &self.repo);
+        let repo = self.repository.clone();
+        let report = ReportOptions::new(self.baseline.clone(), self.peak_gflops, self.peak_gbps);
+        let summary = Benches::new(self.into(), repo, report).run();
+        println!("{summary}");
 
-        let mut handle = Command::new("sh").arg(SH_FILENAME).spawn().unwrap();
-        handle.wait().unwrap();
+        summary.is_success()
     }
 }
 
-impl Into<Vec<BenchParam>> for BenchesArgs {
-    fn into(self) -> Vec<BenchParam> {
-        let mut bench = String::new();
+impl CompareArgs {
+    /// Benchmark the baseline and the candidate, returning whether no regression was found.
+    pub fn run(self) -> bool {
+        if !use_config_file(self.config_file.as_deref()) {
+            return false;
+        }
 
-        match self.bench {
-            Bench::Transformer => bench += "transformer",
-            Bench::MLP => bench += "mlp",
-            Bench::Conv2d => bench += "conv2d",
-            Bench::All => {}
+        if self.baseline.version() == self.candidate.version() {
+            eprintln!("The baseline and the candidate must have different versions");
+            return false;
         }
 
-        if !bench.is_empty() {
-            bench = format!("--bench {bench}");
+        let refs = [self.baseline.clone(), self.candidate.clone()];
+        let params = params(&self.backends, &self.bench, &refs);
+        let summary = Benches::new(params, self.repository.clone(), ReportOptions::default()).run();
+        println!("{summary}");
+
+        let results = summary.results();
+        let of_version = |version: String| -> Vec<&BenchResult> {
+            results
+                .iter()
+                .filter(|result| result.version == version)
+                .copied()
+                .collect()
+        };
+        let baseline = of_version(self.baseline.version());
+        let candidate = of_version(self.candidate.version());
+
+        let options = CompareOptions::new(self.threshold / 100.0, self.significance);
+        let comparisons = compare(&baseline, &candidate, options);
+
+        for comparison in comparisons.iter() {
+            let p_value = comparison
+                .p_value
+                .map(|p_value| format!("{p_value:.3}"))
+                .unwrap_or_else(|| "-".into());
+
+            println!(
+                "{:<32} {:<20} {:>12} -> {:<12} {:>+8.2}%  p={:<6} {}",
+                format!(
+                    "{}/{}",
+                    comparison.baseline.group, comparison.baseline.config
+                ),
+                comparison.baseline.backend,
+                report::format_time(comparison.baseline.mean.estimate),
+                report::format_time(comparison.candidate.mean.estimate),
+                comparison.change * 100.0,
+                p_value,
+                comparison.verdict,
+            );
         }
 
-        let mut runs = Vec::new();
-        for backend in self.backends {
-            let flag = match backend {
-                Backend::Ndarray => "ndarray",
-                Backend::NdarrayNetlib => "ndarray-blas-netlib",
-                Backend::NdarrayOpenblas => "ndarray-blas-openblas",
-                Backend::NdarrayNoStd => "ndarray-no-std",
-                Backend::TchCpu => "tch-cpu",
-                Backend::TchGpu => "tch-gpu",
-                Backend::Wgpu => "wgpu",
-            };
-
-            for tag in self.tags.iter() {
-                runs.push(BenchParam::Git(BenchSettings {
-                    identifier: "tag".into(),
-                    value: tag.into(),
-                    bench: bench.clone(),
-                    backend_flag: flag.into(),
-                }));
-            }
-            for commit in self.commits.iter() {
-                runs.push(BenchParam::Git(BenchSettings {
-                    identifier: "rev".into(),
-                    value: commit.into(),
-                    bench: bench.clone(),
-                    backend_flag: flag.into(),
-                }));
+        let count = |verdict: Verdict| {
+            comparisons
+                .iter()
+                .filter(|comparison| comparison.verdict == verdict)
+                .count()
+        };
+        let regressions = count(Verdict::Regressed);
+        println!(
+            "{} improved, {regressions} regressed, {} unchanged",
+            count(Verdict::Improved),
+            count(Verdict::Unchanged),
+        );
+
+        summary.is_success() && regressions == 0
+    }
+}
+
+impl HistoryFilter {
+    fn matches(&self, entry: &HistoryEntry) -> bool {
+        let result = &entry.result;
+        let matches = |filter: &Option<String>, value: &str| match filter {
+            Some(filter) => filter == value,
+            None => true,
+        };
+
+        matches(&self.suite, &result.suite)
+            && matches(&self.group, &result.group)
+            && matches(&self.config, &result.config)
+            && matches(&self.backend, &result.backend)
+            && matches(&self.machine, &entry.machine)
+            && match &self.revision {
+                Some(revision) => entry.revision.starts_with(revision.as_str()),
+                None => true,
             }
-            for branch in self.branches.iter() {
-                runs.push(BenchParam::Git(BenchSettings {
-                    identifier: "branch".into(),
-                    value: branch.into(),
-                    bench: bench.clone(),
-                    backend_flag: flag.into(),
-                }));
+    }
+}
+
+impl HistoryCommand {
+    /// Execute the command, returning whether it succeeded.
+    pub fn run(self) -> bool {
+        let history = History::default();
+
+        let result = match self {
+            HistoryCommand::List => history.load().map(|entries| list_runs(&entries)),
+            HistoryCommand::Query(filter) => history.load().map(|entries| {
+                entries
+                    .iter()
+                    .filter(|entry| filter.matches(entry))
+                    .for_each(|entry| {
+                        println!(
+                            "{}  {:<24} {:<12} {:<32} {}",
+                            history::format_timestamp(entry.timestamp),
+                            entry.result.bench_id(),
+                            short_revision(&entry.revision),
+                            format!("{}/{}", entry.result.group, entry.result.config),
+                            report::format_time(entry.result.typical.estimate),
+                        )
+                    })
+            }),
+            HistoryCommand::Prune { filter, older_than } => {
+                let now = history::now();
+                let is_old = |entry: &HistoryEntry| match older_than {
+                    Some(days) => now.saturating_sub(entry.timestamp) > days * 86400,
+                    None => true,
+                };
+
+                history
+                    .prune(|entry| filter.matches(entry) && is_old(entry))
+                    .map(|count| println!("Removed {count} results"))
             }
-            for path in self.paths.iter() {
-                runs.push(BenchParam::Path(BenchSettings {
-                    identifier: "path".into(),
-                    value: path.into(),
-                    bench: bench.clone(),
-                    backend_flag: flag.into(),
-                }));
+        };
+
+        match result {
+            Ok(_) => true,
+            Err(err) => {
+                eprintln!("Unable to access the history: {err}");
+                false
             }
         }
-
-        runs
     }
 }
 
-fn write_bash_file(runs: &[BenchParam], filename: &str, repo: &str) {
-    let mut content = String::new();
-
-    for run in runs.iter() {
-        content += match run {
-            BenchParam::Path(path) => build_bash_path(path),
-            BenchParam::Git(git) => build_bash_git(git, repo),
+/// Print one line per recorded run, i.e. per backend and burn version benchmarked together.
+fn list_runs(entries: &[HistoryEntry]) {
+    let mut runs: Vec<(&HistoryEntry, usize)> = Vec::new();
+
+    for entry in entries {
+        let run = runs.iter_mut().find(|(run, _)| {
+            run.timestamp == entry.timestamp
+                && run.machine == entry.machine
+                && run.revision == entry.revision
+                && run.result.bench_id() == entry.result.bench_id()
+        });
+
+        match run {
+            Some((_, count)) => *count += 1,
+            None => runs.push((entry, 1)),
         }
-        .as_str();
     }
-    content += "cat ";
-    for run in runs.iter() {
-        content += run.settings().bench_filename().as_str();
-        content += " ";
+
+    for (run, count) in runs {
+        println!(
+            "{}  {:<24} {:<12} machine {}  {count} results",
+            history::format_timestamp(run.timestamp),
+            run.result.bench_id(),
+            short_revision(&run.revision),
+            run.machine,
+        );
     }
+}
 
-    content += format!("| criterion-table >  {MD_FILENAME}").as_str();
+fn short_revision(revision: &str) -> &str {
+    match revision.char_indices().nth(12) {
+        Some((index, _)) => &revision[..index],
+        None => revision,
+    }
+}
 
-    if let Ok(_) = Command::new("pandoc").arg("--help").output() {
-        content += format!("\npandoc -f markdown {MD_FILENAME} > {HTML_FILENAME}").as_str();
+/// Make the configs of the file available to the benchmarks and to the report, returning whether
+/// the file is valid.
+///
+/// The path is passed through an [environment variable](CONFIG_FILE_ENV), inherited by the
+/// benchmark processes.
+fn use_config_file(path: Option<&Path>) -> bool {
+    let path = match path {
+        Some(path) => path,
+        None => return true,
+    };
+    let check = || -> Result<PathBuf, ConfigFileError> {
+        let path = path.canonicalize()?;
+        tables::check_configs(&ConfigFile::load(&path)?)?;
+        Ok(path)
+    };
+
+    match check() {
+        Ok(path) => {
+            std::env::set_var(CONFIG_FILE_ENV, path);
+            true
+        }
+        Err(err) => {
+            eprintln!("Invalid config file '{}': {err}", path.display());
+            false
+        }
     }
+}
+
+/// Run the command to completion, failing when it returns a non-zero exit status.
+fn run_command(step: &Step, command: &mut Command) -> Result<(), BenchError> {
+    println!("[{step}] {command:?}");
+
+    let status = command.status().map_err(|err| BenchError::io(step, err))?;
 
-    let mut file = File::create(filename).unwrap();
-    write!(file, "{}", content).unwrap();
+    match status.success() {
+        true => Ok(()),
+        false => Err(BenchError::Status {
+            step: step.clone(),
+            status,
+        }),
+    }
 }
 
-fn prepare(runs: &[BenchParam], repo: &str) {
-    std::fs::create_dir_all(OUTPUT_DIR).unwrap();
+/// Write the markdown and html reports of the results.
+fn collect(results: &[BenchResult], options: &ReportOptions) -> Result<(), BenchError> {
+    let step = Step::Collect;
+    let markdown = report::markdown(results, options);
 
-    write_bash_file(runs, SH_FILENAME, repo);
-    make_tables();
+    std::fs::write(MD_FILENAME, &markdown).map_err(|err| BenchError::io(&step, err))?;
+    std::fs::write(HTML_FILENAME, report::html(&markdown)).map_err(|err| BenchError::io(&step, err))
+}
 
-    Command::new("cp")
-        .args(["Cargo.toml", "Cargo-tmp.toml"])
-        .output()
-        .unwrap();
+fn prepare() {
+    std::fs::create_dir_all(OUTPUT_DIR).unwrap();
 }
 
 fn cleanup(params: &[BenchParam]) {
-    Command::new("mv")
-        .args(["Cargo-tmp.toml", "Cargo.toml"])
-        .output()
-        .unwrap();
-
     for run in params.iter() {
         std::fs::remove_file(run.settings().bench_filename()).ok();
     }
-
-    std::fs::remove_file(SH_FILENAME).ok();
-    std::fs::remove_file("tables.toml").ok();
-}
-
-fn build_bash_git(run: &BenchSettings, repo: &str) -> String {
-    let ops = format!("--no-default-features --features {}", run.backend_flag);
-
-    let identifier = &run.identifier;
-    let value = &run.value;
-    let bench = &run.bench;
-
-    let mut output = String::new();
-    let version = run.value.replace('/', "-");
-    output += format!("echo {} > {}\n", version, version_file()).as_str();
-    output += format!("cargo add burn --git {repo} --{identifier} {value}\n").as_str();
-    output += format!("cargo add burn-wgpu --git {repo} --{identifier} {value}\n").as_str();
-    output += format!("cargo add burn-tch --git {repo} --{identifier} {value}\n").as_str();
-    output += format!("cargo add burn-ndarray --git {repo} --{identifier} {value}\n").as_str();
-    output += format!("cargo add burn-autodiff --git {repo} --{identifier} {value}\n").as_str();
-    output += format!(
-        "cargo criterion {ops} {bench} --message-format=json > {}\n",
-        run.bench_filename()
-    )
-    .as_str();
-    println!("Build bash git {}", output);
-    output
-}
-
-fn build_bash_path(run: &BenchSettings) -> String {
-    let ops = format!("--no-default-features --features {}", run.backend_flag);
-
-    let value = &run.value;
-    let bench = &run.bench;
-
-    let mut output = String::new();
-    let version = run.value.replace('/', "-");
-    output += format!("echo {} > {}\n", version, version_file()).as_str();
-    output += format!("cargo add burn --path {value}/burn \n").as_str();
-    output += format!("cargo add burn-wgpu --path {value}/burn-wgpu\n").as_str();
-    output += format!("cargo add burn-tch --path {value}/burn-tch\n").as_str();
-    output += format!("cargo add burn-ndarray --path {value}/burn-ndarray\n").as_str();
-    output += format!("cargo add burn-autodiff --path {value}/burn-autodiff\n").as_str();
-    output += format!(
-        "cargo criterion {ops} {bench} --message-format=json > {}\n",
-        run.bench_filename()
-    )
-    .as_str();
-    println!("Build bash path {}", output);
-    output
 }
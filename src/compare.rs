@@ -0,0 +1,249 @@
+use crate::results::BenchResult;
+use statrs::distribution::{ContinuousCDF, StudentsT};
+
+/// How the candidate performs compared to the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Improved,
+    Regressed,
+    Unchanged,
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Improved => f.write_str("improved"),
+            Verdict::Regressed => f.write_str("regressed"),
+            Verdict::Unchanged => f.write_str("unchanged"),
+        }
+    }
+}
+
+/// The comparison of the same benchmark between the baseline and the candidate.
+pub struct Comparison<'a> {
+    pub baseline: &'a BenchResult,
+    pub candidate: &'a BenchResult,
+    /// Relative change of the mean time, positive when the candidate is slower.
+    pub change: f64,
+    /// Probability of observing such a difference if both means were equal, when enough samples
+    /// are available.
+    pub p_value: Option<f64>,
+    pub verdict: Verdict,
+}
+
+/// Criteria used to classify the differences between the baseline and the candidate.
+#[derive(new, Debug, Clone, Copy)]
+pub struct CompareOptions {
+    /// Minimum relative change of the mean time, e.g. `0.05` for 5%.
+    pub threshold: f64,
+    /// Significance level of the Welch's t-test, e.g. `0.05`.
+    pub significance: f64,
+}
+
+/// Pair up the results of the same suite, group, config and backend, and classify each pair.
+///
+/// A pair is improved or regressed only when the change is statistically significant and
+/// larger than the threshold. When the samples aren't available, the confidence intervals
+/// of the means must not overlap instead.
+pub fn compare<'a>(
+    baseline: &[&'a BenchResult],
+    candidate: &[&'a BenchResult],
+    options: CompareOptions,
+) -> Vec<Comparison<'a>> {
+    baseline
+        .iter()
+        .filter_map(|base| {
+            let other = candidate.iter().find(|other| {
+                other.suite == base.suite
+                    && other.group == base.group
+                    && other.config == base.config
+                    && other.backend == base.backend
+            })?;
+
+            Some(Comparison::new(base, other, options))
+        })
+        .collect()
+}
+
+impl<'a> Comparison<'a> {
+    fn new(baseline: &'a BenchResult, candidate: &'a BenchResult, options: CompareOptions) -> Self {
+        let change = (candidate.mean.estimate - baseline.mean.estimate) / baseline.mean.estimate;
+        let p_value = welch_t_test(&baseline.samples, &candidate.samples);
+
+        let significant = match p_value {
+            Some(p_value) => p_value < options.significance,
+            None => {
+                candidate.mean.lower_bound > baseline.mean.upper_bound
+                    || candidate.mean.upper_bound < baseline.mean.lower_bound
+            }
+        };
+
+        let verdict = match significant {
+            true if change > options.threshold => Verdict::Regressed,
+            true if change < -options.threshold => Verdict::Improved,
+            _ => Verdict::Unchanged,
+        };
+
+        Self {
+            baseline,
+            candidate,
+            change,
+            p_value,
+            verdict,
+        }
+    }
+}
+
+/// Two-sided p-value of the Welch's t-test, which doesn't assume equal variances.
+fn welch_t_test(lhs: &[f64], rhs: &[f64]) -> Option<f64> {
+    if lhs.len() < 2 || rhs.len() < 2 {
+        return None;
+    }
+
+    let (mean_lhs, var_lhs) = mean_variance(lhs);
+    let (mean_rhs, var_rhs) = mean_variance(rhs);
+    let se_lhs = var_lhs / lhs.len() as f64;
+    let se_rhs = var_rhs / rhs.len() as f64;
+    let se = se_lhs + se_rhs;
+
+    if se == 0.0 {
+        return Some(if mean_lhs == mean_rhs { 1.0 } else { 0.0 });
+    }
+
+    let t = (mean_lhs - mean_rhs) / se.sqrt();
+    let freedom = se.powi(2)
+        / (se_lhs.powi(2) / (lhs.len() - 1) as f64 + se_rhs.powi(2) / (rhs.len() - 1) as f64);
+    let distribution = StudentsT::new(0.0, 1.0, freedom).ok()?;
+
+    Some(2.0 * (1.0 - distribution.cdf(t.abs())))
+}
+
+/// Mean and unbiased variance of the values.
+fn mean_variance(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.0);
+
+    (mean, variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::Estimate;
+
+    const BASELINE: [f64; 5] = [100.0, 102.0, 98.0, 101.0, 99.0];
+
+    fn result(samples: &[f64]) -> BenchResult {
+        let (mean, _) = mean_variance(samples);
+
+        BenchResult {
+            samples: samples.to_vec(),
+            ..BenchResult::fixture("mlp", "b32", mean)
+        }
+    }
+
+    fn options() -> CompareOptions {
+        CompareOptions::new(0.05, 0.05)
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * 1e-6,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn identical_samples() {
+        let baseline = result(&BASELINE);
+        let candidate = result(&BASELINE);
+
+        let comparison = Comparison::new(&baseline, &candidate, options());
+
+        assert_eq!(comparison.change, 0.0);
+        assert_close(comparison.p_value.unwrap(), 1.0);
+        assert_eq!(comparison.verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn clearly_faster() {
+        let baseline = result(&BASELINE);
+        let candidate = result(&[80.0, 81.0, 79.0, 82.0, 78.0]);
+
+        let comparison = Comparison::new(&baseline, &candidate, options());
+
+        assert_close(comparison.change, -0.2);
+        // t = 20 with 8 degrees of freedom.
+        assert_close(comparison.p_value.unwrap(), 4.073918328674922e-8);
+        assert_eq!(comparison.verdict, Verdict::Improved);
+    }
+
+    #[test]
+    fn clearly_slower() {
+        let baseline = result(&BASELINE);
+        let candidate = result(&[120.0, 121.0, 119.0, 122.0, 118.0]);
+
+        let comparison = Comparison::new(&baseline, &candidate, options());
+
+        assert_close(comparison.change, 0.2);
+        assert_close(comparison.p_value.unwrap(), 4.073918328674922e-8);
+        assert_eq!(comparison.verdict, Verdict::Regressed);
+    }
+
+    #[test]
+    fn high_variance_not_significant() {
+        let baseline = result(&BASELINE);
+        let candidate = result(&[70.0, 160.0, 80.0, 150.0, 90.0]);
+
+        let comparison = Comparison::new(&baseline, &candidate, options());
+
+        // The change is above the threshold, but could be noise.
+        assert_close(comparison.change, 0.1);
+        // t = -0.534 with 4.011 degrees of freedom.
+        assert_close(comparison.p_value.unwrap(), 0.6214727577093713);
+        assert_eq!(comparison.verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn significant_below_threshold() {
+        let baseline = result(&BASELINE);
+        let candidate = result(&BASELINE.map(|sample| sample * 1.02));
+
+        let comparison = Comparison::new(&baseline, &candidate, options());
+        assert_eq!(comparison.verdict, Verdict::Unchanged);
+
+        let comparison = Comparison::new(&baseline, &candidate, CompareOptions::new(0.01, 0.5));
+        assert_eq!(comparison.verdict, Verdict::Regressed);
+    }
+
+    #[test]
+    fn confidence_intervals_without_samples() {
+        let mut baseline = result(&[]);
+        let mut candidate = result(&[]);
+        baseline.mean = Estimate {
+            estimate: 100.0,
+            lower_bound: 95.0,
+            upper_bound: 105.0,
+            unit: "ns".into(),
+        };
+        candidate.mean = Estimate {
+            estimate: 80.0,
+            lower_bound: 75.0,
+            upper_bound: 85.0,
+            unit: "ns".into(),
+        };
+
+        let comparison = Comparison::new(&baseline, &candidate, options());
+        assert_eq!(comparison.p_value, None);
+        assert_eq!(comparison.verdict, Verdict::Improved);
+
+        candidate.mean.upper_bound = 96.0;
+        let comparison = Comparison::new(&baseline, &candidate, options());
+        assert_eq!(comparison.verdict, Verdict::Unchanged);
+    }
+}
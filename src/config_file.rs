@@ -0,0 +1,265 @@
+use crate::sweep::{self, SweepError};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::ffi::OsStr;
+use std::io;
+use std::path::Path;
+
+/// Environment variable with the path of the config file used by the benchmarks.
+///
+/// The built-in configs of every suite are used when it isn't set.
+pub static CONFIG_FILE_ENV: &str = "BURN_BENCH_CONFIG_FILE";
+
+/// Configs along with the label of their swept values, if any.
+pub type LabeledConfigs<C> = Vec<(Option<String>, C)>;
+
+/// A file listing the configs benchmarked by each suite, in TOML or JSON.
+///
+/// The configs are grouped by suite name, e.g. in TOML:
+///
+/// ```toml
+/// [[mlp]]
+/// batch_size = 32
+/// num_layers = 4
+/// d_model = 1024
+/// ```
+///
+/// The suites missing from the file use their built-in configs. The entries can also
+/// [sweep](crate::sweep::expand) some fields of the config.
+pub struct ConfigFile {
+    suites: Map<String, Value>,
+}
+
+#[derive(Debug)]
+pub enum ConfigFileError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    UnsupportedFormat,
+    NotATable,
+    UnknownSuite(String),
+    InvalidConfigs {
+        suite: String,
+        error: serde_json::Error,
+    },
+    InvalidSweep {
+        suite: String,
+        error: SweepError,
+    },
+    UnsupportedConfig {
+        suite: String,
+        label: String,
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for ConfigFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigFileError::Io(err) => write!(f, "{err}"),
+            ConfigFileError::Toml(err) => write!(f, "{err}"),
+            ConfigFileError::Json(err) => write!(f, "{err}"),
+            ConfigFileError::UnsupportedFormat => {
+                f.write_str("unsupported format, expected a .toml or .json file")
+            }
+            ConfigFileError::NotATable => f.write_str("the configs must be grouped by suite name"),
+            ConfigFileError::UnknownSuite(suite) => write!(f, "unknown suite '{suite}'"),
+            ConfigFileError::InvalidConfigs { suite, error } => {
+                write!(f, "invalid configs for suite '{suite}': {error}")
+            }
+            ConfigFileError::InvalidSweep { suite, error } => {
+                write!(f, "invalid sweep for suite '{suite}': {error}")
+            }
+            ConfigFileError::UnsupportedConfig {
+                suite,
+                label,
+                reason,
+            } => write!(
+                f,
+                "unsupported config '{label}' for suite '{suite}': {reason}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigFileError {}
+
+impl From<io::Error> for ConfigFileError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl ConfigFile {
+    /// Load the file, its format is deduced from the extension.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigFileError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+
+        let value: Value = match path.extension().and_then(OsStr::to_str) {
+            Some("toml") => toml::from_str(&content).map_err(ConfigFileError::Toml)?,
+            Some("json") => serde_json::from_str(&content).map_err(ConfigFileError::Json)?,
+            _ => return Err(ConfigFileError::UnsupportedFormat),
+        };
+
+        match value {
+            Value::Object(suites) => Ok(Self { suites }),
+            _ => Err(ConfigFileError::NotATable),
+        }
+    }
+
+    /// The names of the suites listed by the file.
+    pub fn suites(&self) -> impl Iterator<Item = &str> {
+        self.suites.keys().map(String::as_str)
+    }
+
+    /// The configs of the suite, labeled by their swept values, none when the file doesn't list
+    /// the suite.
+    pub fn configs<C: DeserializeOwned>(
+        &self,
+        suite: &str,
+    ) -> Result<Option<LabeledConfigs<C>>, ConfigFileError> {
+        let invalid_configs = |error| ConfigFileError::InvalidConfigs {
+            suite: suite.into(),
+            error,
+        };
+        let entries: Vec<Value> = match self.suites.get(suite) {
+            Some(entries) => serde_json::from_value(entries.clone()).map_err(invalid_configs)?,
+            None => return Ok(None),
+        };
+
+        sweep::expand(&entries)
+            .map_err(|error| ConfigFileError::InvalidSweep {
+                suite: suite.into(),
+                error,
+            })?
+            .into_iter()
+            .map(|(label, config)| {
+                serde_json::from_value(config)
+                    .map(|config| (label, config))
+                    .map_err(invalid_configs)
+            })
+            .collect::<Result<_, _>>()
+            .map(Some)
+    }
+}
+
+/// The configs of the suite listed by the file of the [environment variable](CONFIG_FILE_ENV),
+/// none when it isn't set or when the file doesn't list the suite.
+///
+/// # Panics
+///
+/// If the file can't be loaded or if its configs for the suite are invalid.
+pub fn configs_from_env<C: DeserializeOwned>(suite: &str) -> Option<LabeledConfigs<C>> {
+    let path = std::env::var_os(CONFIG_FILE_ENV)?;
+
+    ConfigFile::load(&path)
+        .and_then(|file| file.configs(suite))
+        .unwrap_or_else(|err| panic!("Invalid config file {path:?}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use tempfile::TempDir;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct MlpConfig {
+        batch_size: usize,
+        d_model: usize,
+    }
+
+    fn load(name: &str, content: &str) -> (TempDir, Result<ConfigFile, ConfigFileError>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+
+        (dir, ConfigFile::load(path))
+    }
+
+    fn mlp_configs(file: &ConfigFile) -> Vec<(Option<String>, MlpConfig)> {
+        file.configs("mlp").unwrap().unwrap()
+    }
+
+    #[test]
+    fn toml_and_json_files() {
+        let (_dir, toml) = load("configs.toml", "[[mlp]]\nbatch_size = 32\nd_model = 1024\n");
+        let (_dir, json) = load(
+            "configs.json",
+            r#"{ "mlp": [{ "batch_size": 32, "d_model": 1024 }] }"#,
+        );
+        let expected = vec![(
+            None,
+            MlpConfig {
+                batch_size: 32,
+                d_model: 1024,
+            },
+        )];
+
+        assert_eq!(mlp_configs(&toml.unwrap()), expected);
+        assert_eq!(mlp_configs(&json.unwrap()), expected);
+    }
+
+    #[test]
+    fn unsupported_formats() {
+        let (_dir, yaml) = load("configs.yaml", "mlp: []");
+        let (_dir, list) = load("configs.json", "[]");
+
+        assert!(matches!(yaml, Err(ConfigFileError::UnsupportedFormat)));
+        assert!(matches!(list, Err(ConfigFileError::NotATable)));
+    }
+
+    #[test]
+    fn suites_listed_by_the_file() {
+        let (_dir, file) = load(
+            "configs.toml",
+            "[[mlp]]\nbatch_size = 1\nd_model = 1\n\n[[unknown]]\n",
+        );
+        let file = file.unwrap();
+
+        assert_eq!(file.suites().collect::<Vec<_>>(), vec!["mlp", "unknown"]);
+        assert!(file.configs::<MlpConfig>("matmul").unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_configs() {
+        let (_dir, file) = load(
+            "configs.toml",
+            "[[mlp]]\nbatch_size = \"large\"\nd_model = 1\n",
+        );
+
+        assert!(matches!(
+            file.unwrap().configs::<MlpConfig>("mlp"),
+            Err(ConfigFileError::InvalidConfigs { suite, .. }) if suite == "mlp"
+        ));
+    }
+
+    #[test]
+    fn swept_configs_are_labeled() {
+        let (_dir, file) = load(
+            "configs.toml",
+            "[[mlp]]\nd_model = 64\n\n[mlp.sweep]\nbatch_size = [1, 32]\n",
+        );
+
+        assert_eq!(
+            mlp_configs(&file.unwrap()),
+            vec![
+                (
+                    Some("batch_size=1".into()),
+                    MlpConfig {
+                        batch_size: 1,
+                        d_model: 64
+                    }
+                ),
+                (
+                    Some("batch_size=32".into()),
+                    MlpConfig {
+                        batch_size: 32,
+                        d_model: 64
+                    }
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::results::BenchResult;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static HISTORY_FILENAME: &str = "target/burn_benches/history/runs.jsonl";
+
+/// A benchmark result recorded in the [history](History).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch when the run finished.
+    pub timestamp: u64,
+    /// Commit of burn the benchmark was compiled with.
+    pub revision: String,
+    /// Fingerprint of the machine the benchmark was executed on.
+    pub machine: String,
+    #[serde(flatten)]
+    pub result: BenchResult,
+}
+
+impl HistoryEntry {
+    pub fn new(result: BenchResult, revision: String, machine: String, timestamp: u64) -> Self {
+        Self {
+            timestamp,
+            revision,
+            machine,
+            result,
+        }
+    }
+}
+
+/// Append-only store of all the benchmark results, saved as JSON lines.
+pub struct History {
+    path: PathBuf,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(HISTORY_FILENAME)
+    }
+}
+
+impl History {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn append(&self, entries: &[HistoryEntry]) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        for entry in entries {
+            writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Load all the entries, from the oldest to the most recent.
+    pub fn load(&self) -> io::Result<Vec<HistoryEntry>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        BufReader::new(file)
+            .lines()
+            .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+            .map(|line| -> io::Result<HistoryEntry> { Ok(serde_json::from_str(&line?)?) })
+            .collect()
+    }
+
+    /// Remove the entries matching the predicate, returning how many were removed.
+    pub fn prune<F: Fn(&HistoryEntry) -> bool>(&self, predicate: F) -> io::Result<usize> {
+        let entries = self.load()?;
+        let (removed, kept): (Vec<_>, Vec<_>) = entries.into_iter().partition(predicate);
+
+        let mut content = String::new();
+        for entry in kept.iter() {
+            content += serde_json::to_string(entry)?.as_str();
+            content += "\n";
+        }
+
+        // Write to a temporary file first, so the history is never left half written.
+        let tmp = self.path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, content)?;
+        std::fs::rename(&tmp, &self.path)?;
+
+        Ok(removed.len())
+    }
+}
+
+/// Current time in seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// Format a timestamp in seconds since the unix epoch as an UTC date.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+
+    // Civil from days algorithm from Howard Hinnant.
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(config: &str, timestamp: u64) -> HistoryEntry {
+        let result = BenchResult::fixture("mlp", config, 1.0);
+
+        HistoryEntry::new(result, "abc123".into(), "machine".into(), timestamp)
+    }
+
+    #[test]
+    fn format_epoch() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+    }
+
+    #[test]
+    fn format_leap_day() {
+        assert_eq!(format_timestamp(1709210096), "2024-02-29 12:34:56");
+        assert_eq!(format_timestamp(1709210096 + 86400), "2024-03-01 12:34:56");
+    }
+
+    #[test]
+    fn format_year_boundary() {
+        assert_eq!(format_timestamp(946684799), "1999-12-31 23:59:59");
+        assert_eq!(format_timestamp(946684800), "2000-01-01 00:00:00");
+    }
+
+    #[test]
+    fn prune_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::new(dir.path().join("runs.jsonl"));
+
+        history
+            .append(&[entry("a", 10), entry("b", 20), entry("c", 30)])
+            .unwrap();
+        let removed = history.prune(|entry| entry.timestamp < 25).unwrap();
+        let configs: Vec<_> = history
+            .load()
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.result.config)
+            .collect();
+
+        assert_eq!(removed, 2);
+        assert_eq!(configs, vec!["c"]);
+    }
+}
@@ -1,74 +1,30 @@
 #[macro_use]
 extern crate derive_new;
 
+pub mod backend;
 pub mod bench;
 pub mod cli;
+pub mod compare;
+pub mod config_file;
+pub mod history;
+pub mod report;
+pub mod results;
+pub mod sweep;
 pub mod tables;
+pub mod workspace;
 
 mod benches;
 pub use benches::*;
 
-#[cfg(feature = "wgpu")]
-pub type BenchBackend = burn_wgpu::Wgpu<burn_wgpu::Vulkan, f32, i32>;
-#[cfg(feature = "tch-cpu")]
-pub type BenchBackend = burn_tch::LibTorch<f32>;
-#[cfg(feature = "tch-gpu")]
-pub type BenchBackend = burn_tch::LibTorch<f32>;
-
-#[cfg(any(
-    feature = "ndarray",
-    feature = "ndarray-blas-netlib",
-    feature = "ndarray-blas-openblas",
-    feature = "ndarray-no-std"
-))]
-pub type BenchBackend = burn_ndarray::NdArray<f32>;
-
-pub type BenchDevice = <BenchBackend as burn::tensor::backend::Backend>::Device;
-
 pub fn bench_id() -> String {
     format!("{}:{}", flags(), version())
 }
 
+/// The flag of the selected backend, which [run_suite](backend::run_suite) checks beforehand.
 pub fn flags() -> String {
-    #[cfg(feature = "tch-cpu")]
-    return "tch-cpu".into();
-
-    #[cfg(feature = "tch-gpu")]
-    return "tch-gpu".into();
-
-    #[cfg(feature = "ndarray")]
-    return "ndarray".into();
-
-    #[cfg(feature = "ndarray-blas-netlib")]
-    return "ndarray-netlib".into();
-
-    #[cfg(feature = "ndarray-blas-openblas")]
-    return "ndarray-openblas".into();
-
-    #[cfg(feature = "wgpu")]
-    return "wgpu".into();
-
-    #[cfg(feature = "ndarray-no-std")]
-    return "ndarray-no-std".into();
-}
-
-pub fn device() -> BenchDevice {
-    #[cfg(feature = "tch-gpu")]
-    return burn_tch::LibTorchDevice::Cuda(0);
-
-    #[cfg(feature = "tch-cpu")]
-    return burn_tch::LibTorchDevice::Cpu;
-
-    #[cfg(feature = "wgpu")]
-    return burn_wgpu::WgpuDevice::DiscreteGpu(0);
-
-    #[cfg(any(
-        feature = "ndarray",
-        feature = "ndarray-blas-netlib",
-        feature = "ndarray-blas-openblas",
-        feature = "ndarray-no-std"
-    ))]
-    return burn_ndarray::NdArrayDevice::Cpu;
+    backend::BackendKind::selected()
+        .map(|kind| kind.flag().into())
+        .unwrap_or_else(|err| panic!("{err}"))
 }
 
 pub fn version_file() -> String {
@@ -80,3 +36,11 @@ pub fn version() -> String {
     let version = version.trim();
     version.to_string()
 }
+
+/// Hash of the content, which stays the same across builds unlike the std hasher.
+pub fn stable_hash(content: &[u8]) -> u64 {
+    // FNV-1a
+    content.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -1,10 +1,16 @@
-use burn_benches::cli::{Benches, BenchesArgs};
+use burn_benches::cli::{BenchesCli, BenchesCommand};
 use clap::Parser;
 
 fn main() {
-    let args = BenchesArgs::parse();
-    let repo = args.repository.clone();
-    let benches = Benches::new(args.into(), repo);
+    let cli = BenchesCli::parse();
 
-    benches.run();
+    let success = match cli.command {
+        Some(BenchesCommand::History(command)) => command.run(),
+        Some(BenchesCommand::Compare(args)) => args.run(),
+        None => cli.args.run(),
+    };
+
+    if !success {
+        std::process::exit(1);
+    }
 }
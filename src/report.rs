@@ -0,0 +1,347 @@
+use crate::results::{BenchResult, Throughput};
+use crate::tables::{suites, system_infos, SuiteInfo};
+
+/// Options of the reports.
+#[derive(new, Debug, Clone, Default)]
+pub struct ReportOptions {
+    /// Column used as the reference for the speedups, formatted as `backend:version`.
+    pub baseline: Option<String>,
+    /// Peak compute of the machine, in GFLOP/s.
+    pub peak_gflops: Option<f64>,
+    /// Peak memory bandwidth of the machine, in GB/s.
+    pub peak_gbps: Option<f64>,
+}
+
+/// Render the markdown report comparing all the results.
+///
+/// Each criterion group gets its own table with one row per config and one column per
+/// `backend:version`. Every column shows its speedup relative to the `baseline` column, which
+/// defaults to the first one, and the throughput of the benchmarks reporting their work, along
+/// with the fraction of the peak of the machine when it is given.
+pub fn markdown(results: &[BenchResult], options: &ReportOptions) -> String {
+    let suites = suites();
+    let mut report = String::from("# Burn Benchmarks\n\nBurn micro benchmarks\n");
+    report += system_infos().as_str();
+
+    for group in unique(results.iter().map(|result| result.group.as_str())) {
+        let results: Vec<_> = results
+            .iter()
+            .filter(|result| result.group == group)
+            .collect();
+
+        report += format!("\n## {group}\n\n").as_str();
+
+        let suite = suite_of(&suites, &results[0].suite);
+
+        if let Some(suite) = suite {
+            report += suite.details.trim_end();
+            report += "\n\n";
+        }
+
+        let element = suite.and_then(|suite| suite.element);
+        report += table(&results, element, options).as_str();
+    }
+
+    report
+}
+
+/// Render the HTML version of a report produced by [markdown].
+///
+/// Only the subset of markdown used by the reports is supported: headings, lists, tables,
+/// paragraphs and inline code.
+pub fn html(markdown: &str) -> String {
+    let mut html =
+        String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Burn Benchmarks</title>\n</head>\n<body>\n");
+    let mut lines = markdown.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            html += format!("<h2>{}</h2>\n", inline(heading)).as_str();
+        } else if let Some(heading) = line.strip_prefix("# ") {
+            html += format!("<h1>{}</h1>\n", inline(heading)).as_str();
+        } else if line.starts_with("- ") {
+            html += "<ul>\n";
+            let mut item = Some(line);
+            while let Some(line) = item {
+                html += format!("<li>{}</li>\n", inline(&line[2..])).as_str();
+                item = lines.next_if(|line| line.starts_with("- "));
+            }
+            html += "</ul>\n";
+        } else if line.starts_with('|') {
+            html += "<table>\n";
+            html += format!("<tr>{}</tr>\n", cells(line, "th")).as_str();
+            lines.next_if(|line| line.starts_with("|-"));
+            while let Some(line) = lines.next_if(|line| line.starts_with('|')) {
+                html += format!("<tr>{}</tr>\n", cells(line, "td")).as_str();
+            }
+            html += "</table>\n";
+        } else if !line.trim().is_empty() {
+            html += format!("<p>{}</p>\n", inline(line)).as_str();
+        }
+    }
+
+    html += "</body>\n</html>\n";
+    html
+}
+
+fn table(results: &[&BenchResult], element: Option<&str>, options: &ReportOptions) -> String {
+    let columns = unique(results.iter().map(|result| result.bench_id()));
+    let configs = unique(results.iter().map(|result| result.config.clone()));
+    let baseline = options
+        .baseline
+        .clone()
+        .filter(|baseline| columns.contains(baseline))
+        .unwrap_or_else(|| columns[0].clone());
+
+    let find = |config: &str, column: &str| {
+        results
+            .iter()
+            .find(|result| result.config == config && result.bench_id() == column)
+    };
+
+    let mut table = String::from("| Config |");
+    columns
+        .iter()
+        .for_each(|column| table += format!(" `{column}` |").as_str());
+    table += "\n|--------|";
+    columns.iter().for_each(|_| table += "--------|");
+    table += "\n";
+
+    for config in configs.iter() {
+        table += format!("| {config} |").as_str();
+
+        for column in columns.iter() {
+            let mut cell = match (find(config, column), find(config, &baseline)) {
+                (Some(result), Some(_)) if *column == baseline => {
+                    format!("`{}` (baseline)", format_time(result.typical.estimate))
+                }
+                (Some(result), Some(base)) => {
+                    let time = result.typical.estimate;
+                    let speedup = base.typical.estimate / time;
+
+                    format!("`{}` ({})", format_time(time), format_speedup(speedup))
+                }
+                (Some(result), None) => format!("`{}`", format_time(result.typical.estimate)),
+                (None, _) => "N/A".into(),
+            };
+
+            if let Some(result) = find(config, column) {
+                result.throughput.iter().for_each(|throughput| {
+                    cell += format_throughput(result, throughput, element, options).as_str()
+                });
+            }
+            table += format!(" {cell} |").as_str();
+        }
+
+        table += "\n";
+    }
+
+    table
+}
+
+fn suite_of<'a>(suites: &'a [SuiteInfo], name: &str) -> Option<&'a SuiteInfo> {
+    suites.iter().find(|suite| suite.name == name)
+}
+
+/// Collect the distinct items, keeping the order of their first appearance.
+fn unique<T: PartialEq>(items: impl Iterator<Item = T>) -> Vec<T> {
+    let mut output = Vec::new();
+
+    for item in items {
+        if !output.contains(&item) {
+            output.push(item);
+        }
+    }
+
+    output
+}
+
+/// Format a duration given in nanoseconds.
+pub fn format_time(nanos: f64) -> String {
+    match nanos {
+        nanos if nanos < 1e3 => format!("{nanos:.2} ns"),
+        nanos if nanos < 1e6 => format!("{:.2} us", nanos / 1e3),
+        nanos if nanos < 1e9 => format!("{:.2} ms", nanos / 1e6),
+        nanos => format!("{:.2} s", nanos / 1e9),
+    }
+}
+
+/// Format the throughput of the result, e.g. `, 12.34 GFLOP/s (45.6% of peak)`.
+///
+/// Elements are counted as floating point operations, unless the suite names them, in which
+/// case the time per element is also shown, e.g. `, 1234.56 token/s (810.00 us/token)`.
+fn format_throughput(
+    result: &BenchResult,
+    throughput: &Throughput,
+    element: Option<&str>,
+    options: &ReportOptions,
+) -> String {
+    // Work per nanosecond, i.e. in giga units per second.
+    let rate = throughput.per_iteration as f64 / result.typical.estimate;
+    let (unit, peak) = match (throughput.unit.as_str(), element) {
+        ("elements", Some(element)) => {
+            return format!(
+                ", {:.2} {element}/s ({}/{element})",
+                rate * 1e9,
+                format_time(1.0 / rate)
+            )
+        }
+        ("elements", None) => ("GFLOP/s", options.peak_gflops),
+        (unit, _) if unit.starts_with("bytes") => ("GB/s", options.peak_gbps),
+        _ => return String::new(),
+    };
+
+    let percent = peak.map(|peak| rate / peak * 100.0);
+    // The bandwidths under 1 GB/s, e.g. of the disk, are shown in MB/s.
+    let (rate, unit) = match unit {
+        "GB/s" if rate < 1.0 => (rate * 1e3, "MB/s"),
+        unit => (rate, unit),
+    };
+
+    match percent {
+        Some(percent) => format!(", {rate:.2} {unit} ({percent:.1}% of peak)"),
+        None => format!(", {rate:.2} {unit}"),
+    }
+}
+
+fn format_speedup(speedup: f64) -> String {
+    match speedup {
+        speedup if speedup >= 1.0 => format!("{speedup:.2}x faster"),
+        speedup => format!("{:.2}x slower", 1.0 / speedup),
+    }
+}
+
+fn cells(line: &str, tag: &str) -> String {
+    line.trim()
+        .trim_matches('|')
+        .split('|')
+        .map(|cell| format!("<{tag}>{}</{tag}>", inline(cell.trim())))
+        .collect()
+}
+
+/// Escape the text and convert the inline code spans.
+fn inline(text: &str) -> String {
+    let escaped = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+
+    escaped
+        .split('`')
+        .enumerate()
+        .map(|(i, part)| match i % 2 {
+            1 => format!("<code>{part}</code>"),
+            _ => part.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(
+        config: &str,
+        backend: &str,
+        nanos: f64,
+        throughput: Option<(u64, &str)>,
+    ) -> BenchResult {
+        BenchResult {
+            backend: backend.into(),
+            throughput: throughput
+                .into_iter()
+                .map(|(per_iteration, unit)| Throughput {
+                    per_iteration,
+                    unit: unit.into(),
+                })
+                .collect(),
+            ..BenchResult::fixture("matmul", config, nanos)
+        }
+    }
+
+    #[test]
+    fn table_with_baseline_and_peaks() {
+        let results = [
+            result("flops", "ndarray", 2000.0, Some((4000, "elements"))),
+            result("flops", "wgpu", 1000.0, Some((4000, "elements"))),
+            result("bytes", "ndarray", 500.0, Some((1000, "bytes"))),
+            result("bytes", "wgpu", 2000.0, Some((1000, "bytes"))),
+            result("plain", "wgpu", 1.5e6, None),
+        ];
+        let results: Vec<_> = results.iter().collect();
+        let options = ReportOptions::new(Some("wgpu:0.11.0".into()), Some(8.0), Some(4.0));
+
+        assert_eq!(
+            table(&results, None, &options),
+            "\
+| Config | `ndarray:0.11.0` | `wgpu:0.11.0` |
+|--------|--------|--------|
+| flops | `2.00 us` (2.00x slower), 2.00 GFLOP/s (25.0% of peak) | `1.00 us` (baseline), 4.00 GFLOP/s (50.0% of peak) |
+| bytes | `500.00 ns` (4.00x faster), 2.00 GB/s (50.0% of peak) | `2.00 us` (baseline), 500.00 MB/s (12.5% of peak) |
+| plain | N/A | `1.50 ms` (baseline) |
+"
+        );
+    }
+
+    #[test]
+    fn table_with_named_elements() {
+        let results = [
+            result("step", "ndarray", 4e6, Some((2, "elements"))),
+            result("step", "tch-cpu", 2e6, Some((2, "elements"))),
+        ];
+        let results: Vec<_> = results.iter().collect();
+
+        assert_eq!(
+            table(&results, Some("token"), &ReportOptions::default()),
+            "\
+| Config | `ndarray:0.11.0` | `tch-cpu:0.11.0` |
+|--------|--------|--------|
+| step | `4.00 ms` (baseline), 500.00 token/s (2.00 ms/token) | `2.00 ms` (2.00x faster), 1000.00 token/s (1.00 ms/token) |
+"
+        );
+    }
+
+    #[test]
+    fn html_of_markdown() {
+        let markdown = "\
+# Burn Benchmarks
+
+## matmul
+
+Matmul <f32> benchmarks.
+
+- b1-m2 => `MatmulConfig`
+- b1-m4 => `MatmulConfig`
+
+| Config | `ndarray:0.11.0` |
+|--------|--------|
+| b1-m2 | `1.00 us` (baseline) |
+";
+
+        assert_eq!(
+            html(markdown),
+            "\
+<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Burn Benchmarks</title>
+</head>
+<body>
+<h1>Burn Benchmarks</h1>
+<h2>matmul</h2>
+<p>Matmul &lt;f32&gt; benchmarks.</p>
+<ul>
+<li>b1-m2 =&gt; <code>MatmulConfig</code></li>
+<li>b1-m4 =&gt; <code>MatmulConfig</code></li>
+</ul>
+<table>
+<tr><th>Config</th><th><code>ndarray:0.11.0</code></th></tr>
+<tr><td>b1-m2</td><td><code>1.00 us</code> (baseline)</td></tr>
+</table>
+</body>
+</html>
+"
+        );
+    }
+}
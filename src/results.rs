@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// The result of one benchmark case, parsed from a criterion `benchmark-complete` message.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BenchResult {
+    /// Name of the suite, e.g. `mlp`.
+    pub suite: String,
+    /// Name of the criterion group, e.g. `mlp-autodiff`.
+    pub group: String,
+    /// Identifier of the benchmarked config in the suite.
+    pub config: String,
+    /// Backend flag, e.g. `ndarray`.
+    pub backend: String,
+    /// Burn version the benchmark was compiled with.
+    pub version: String,
+    pub typical: Estimate,
+    pub mean: Estimate,
+    pub median: Estimate,
+    pub throughput: Vec<Throughput>,
+    /// Average time of one iteration for each sample, in nanoseconds.
+    #[serde(default)]
+    pub samples: Vec<f64>,
+}
+
+impl BenchResult {
+    /// The column name of the benchmark, matching [bench_id](crate::bench_id).
+    pub fn bench_id(&self) -> String {
+        format!("{}:{}", self.backend, self.version)
+    }
+}
+
+/// A statistical estimate with its confidence interval.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Estimate {
+    pub estimate: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub unit: String,
+}
+
+/// The amount of work done by one iteration of a benchmark.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Throughput {
+    pub per_iteration: u64,
+    pub unit: String,
+}
+
+#[derive(Debug)]
+pub enum ResultsError {
+    Io(io::Error),
+    Json {
+        line: usize,
+        error: serde_json::Error,
+    },
+    InvalidId(String),
+}
+
+impl std::fmt::Display for ResultsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResultsError::Io(err) => write!(f, "{err}"),
+            ResultsError::Json { line, error } => {
+                write!(f, "invalid message at line {line}: {error}")
+            }
+            ResultsError::InvalidId(id) => write!(f, "invalid benchmark id '{id}'"),
+        }
+    }
+}
+
+impl std::error::Error for ResultsError {}
+
+impl From<io::Error> for ResultsError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum Message {
+    BenchmarkComplete(Box<BenchmarkComplete>),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct BenchmarkComplete {
+    id: String,
+    typical: Estimate,
+    mean: Estimate,
+    median: Estimate,
+    #[serde(default)]
+    throughput: Vec<Throughput>,
+    iteration_count: Vec<f64>,
+    measured_values: Vec<f64>,
+}
+
+/// Load the results from a file written by `cargo criterion --message-format=json`.
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<BenchResult>, ResultsError> {
+    parse(BufReader::new(File::open(path)?))
+}
+
+/// Parse the messages of `cargo criterion --message-format=json`, ignoring everything that isn't
+/// a completed benchmark.
+pub fn parse<R: BufRead>(reader: R) -> Result<Vec<BenchResult>, ResultsError> {
+    let mut results = Vec::new();
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message = serde_json::from_str(&line)
+            .map_err(|error| ResultsError::Json { line: i + 1, error })?;
+
+        if let Message::BenchmarkComplete(message) = message {
+            results.push(BenchResult::try_from(*message)?);
+        }
+    }
+
+    Ok(results)
+}
+
+impl TryFrom<BenchmarkComplete> for BenchResult {
+    type Error = ResultsError;
+
+    fn try_from(message: BenchmarkComplete) -> Result<Self, Self::Error> {
+        // Benchmark ids are formatted as `{group}/{backend}:{version}/{config}`.
+        let invalid_id = || ResultsError::InvalidId(message.id.clone());
+
+        let mut parts = message.id.rsplitn(3, '/');
+        let config = parts.next().ok_or_else(invalid_id)?;
+        let function = parts.next().ok_or_else(invalid_id)?;
+        let group = parts.next().ok_or_else(invalid_id)?;
+        let (backend, version) = function.split_once(':').ok_or_else(invalid_id)?;
+        let suite = group.split('-').next().unwrap_or(group);
+        let samples = message
+            .measured_values
+            .iter()
+            .zip(message.iteration_count.iter())
+            .map(|(value, count)| value / count)
+            .collect();
+
+        Ok(Self {
+            suite: suite.into(),
+            group: group.into(),
+            config: config.into(),
+            backend: backend.into(),
+            version: version.into(),
+            typical: message.typical,
+            mean: message.mean,
+            median: message.median,
+            throughput: message.throughput,
+            samples,
+        })
+    }
+}
+
+#[cfg(test)]
+impl BenchResult {
+    /// A result of the suite on `ndarray`, whose estimates all equal the given nanoseconds.
+    pub(crate) fn fixture(suite: &str, config: &str, nanos: f64) -> Self {
+        let estimate = Estimate {
+            estimate: nanos,
+            lower_bound: nanos,
+            upper_bound: nanos,
+            unit: "ns".into(),
+        };
+
+        Self {
+            suite: suite.into(),
+            group: suite.into(),
+            config: config.into(),
+            backend: "ndarray".into(),
+            version: "0.11.0".into(),
+            typical: estimate.clone(),
+            mean: estimate.clone(),
+            median: estimate,
+            throughput: Vec::new(),
+            samples: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str) -> String {
+        let estimate =
+            r#"{"estimate":1500.0,"lower_bound":1400.0,"upper_bound":1600.0,"unit":"ns"}"#;
+
+        format!(
+            r#"{{"reason":"benchmark-complete","id":"{id}","report_directory":"target/criterion","iteration_count":[10,20],"measured_values":[15000.0,28000.0],"unit":"ns","throughput":[{{"per_iteration":4096,"unit":"elements"}}],"typical":{estimate},"mean":{estimate},"median":{estimate},"median_abs_dev":{estimate},"slope":null,"change":null}}"#
+        )
+    }
+
+    #[test]
+    fn parse_benchmark_complete() {
+        let input = [
+            r#"{"reason":"group-complete","group_name":"mlp","benchmarks":[],"report_directory":""}"#
+                .to_string(),
+            String::new(),
+            message("mlp/ndarray:0.11.0/b32-l3-d256"),
+        ]
+        .join("\n");
+
+        let results = parse(input.as_bytes()).unwrap();
+
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!(result.suite, "mlp");
+        assert_eq!(result.group, "mlp");
+        assert_eq!(result.config, "b32-l3-d256");
+        assert_eq!(result.backend, "ndarray");
+        assert_eq!(result.version, "0.11.0");
+        assert_eq!(result.bench_id(), "ndarray:0.11.0");
+        assert_eq!(result.mean.estimate, 1500.0);
+        assert_eq!(result.throughput[0].per_iteration, 4096);
+        assert_eq!(result.throughput[0].unit, "elements");
+        assert_eq!(result.samples, vec![1500.0, 1400.0]);
+    }
+
+    #[test]
+    fn parse_dashed_group() {
+        let input = message("data_movement-autodiff/tch-gpu:main/transpose-1024x1024-a1b2c3d4");
+
+        let results = parse(input.as_bytes()).unwrap();
+
+        let result = &results[0];
+        assert_eq!(result.suite, "data_movement");
+        assert_eq!(result.group, "data_movement-autodiff");
+        assert_eq!(result.config, "transpose-1024x1024-a1b2c3d4");
+        assert_eq!(result.backend, "tch-gpu");
+        assert_eq!(result.version, "main");
+    }
+
+    #[test]
+    fn parse_invalid_id() {
+        let input = message("mlp/ndarray/b32-l3-d256");
+
+        match parse(input.as_bytes()) {
+            Err(ResultsError::InvalidId(id)) => assert_eq!(id, "mlp/ndarray/b32-l3-d256"),
+            other => panic!("expected an invalid id, got {other:?}"),
+        }
+
+        let input = message("b32-l3-d256");
+
+        assert!(matches!(
+            parse(input.as_bytes()),
+            Err(ResultsError::InvalidId(_))
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_json() {
+        let input = "\n{\"reason\":";
+
+        assert!(matches!(
+            parse(input.as_bytes()),
+            Err(ResultsError::Json { line: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,435 @@
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+
+/// Key of the sweep in the entries of the [config file](crate::config_file::ConfigFile).
+static SWEEP_KEY: &str = "sweep";
+/// Maximum number of values swept for a field.
+const MAX_VALUES: usize = 1024;
+/// Maximum number of configs described by a sweep.
+const MAX_CONFIGS: usize = 4096;
+
+/// Expand the entries listed for a suite to the configs they describe, along with their labels.
+///
+/// An entry is either a config or a config whose `sweep` maps some of its fields to their values.
+/// A sweep is expanded to the cartesian product of the values, each config being labeled by the
+/// values of the swept fields, e.g. `batch_size=32,d_model=1024`.
+///
+/// The values of a field are either listed or given as an inclusive range, incremented by `step`
+/// or multiplied by `factor`:
+///
+/// ```toml
+/// [[mlp]]
+/// num_layers = 4
+///
+/// [mlp.sweep]
+/// batch_size = [1, 32, 512]
+/// d_model = { start = 64, end = 4096, factor = 2 }
+/// ```
+///
+/// Nested fields are swept with their dotted path, e.g. `"encoder.d_model"`. A field can't sweep
+/// more than 1024 values, nor a sweep describe more than 4096 configs.
+pub fn expand(entries: &[Value]) -> Result<Vec<(Option<String>, Value)>, SweepError> {
+    let mut cases = Vec::new();
+
+    for entry in entries.iter() {
+        let mut base = entry.clone();
+        let sweep = match base
+            .as_object_mut()
+            .and_then(|entry| entry.remove(SWEEP_KEY))
+        {
+            Some(Value::Object(sweep)) => sweep,
+            Some(_) => return Err(SweepError::NotAMap),
+            None => {
+                cases.push((None, base));
+                continue;
+            }
+        };
+
+        let axes = sweep
+            .into_iter()
+            .map(|(field, values)| values_of(&field, values).map(|values| (field, values)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let configs = axes
+            .iter()
+            .try_fold(1usize, |configs, (_, values)| {
+                configs.checked_mul(values.len())
+            })
+            .filter(|configs| *configs <= MAX_CONFIGS);
+        if configs.is_none() {
+            return Err(SweepError::TooManyConfigs);
+        }
+
+        for combination in product(&axes) {
+            let mut config = base.clone();
+            let mut label = Vec::with_capacity(axes.len());
+
+            for ((field, _), value) in axes.iter().zip(combination) {
+                set_field(&mut config, field, value.clone())?;
+                label.push(format!("{field}={}", format_value(value)));
+            }
+
+            cases.push((Some(label.join(",")), config));
+        }
+    }
+
+    Ok(cases)
+}
+
+#[derive(Debug)]
+pub enum SweepError {
+    NotAMap,
+    NoValues(String),
+    InvalidValues(String),
+    InvalidRange { field: String, reason: &'static str },
+    InvalidField(String),
+    TooManyValues(String),
+    TooManyConfigs,
+}
+
+impl std::fmt::Display for SweepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SweepError::NotAMap => f.write_str("the sweep must map the fields to their values"),
+            SweepError::NoValues(field) => write!(f, "no values to sweep for '{field}'"),
+            SweepError::InvalidValues(field) => write!(
+                f,
+                "the values of '{field}' must be a list or a range with a step or a factor"
+            ),
+            SweepError::InvalidRange { field, reason } => {
+                write!(f, "invalid range for '{field}': {reason}")
+            }
+            SweepError::InvalidField(field) => write!(f, "'{field}' isn't a field of the config"),
+            SweepError::TooManyValues(field) => {
+                write!(f, "more than {MAX_VALUES} values to sweep for '{field}'")
+            }
+            SweepError::TooManyConfigs => {
+                write!(f, "the sweep describes more than {MAX_CONFIGS} configs")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SweepError {}
+
+/// An inclusive range of values.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Range {
+    start: Number,
+    end: Number,
+    step: Option<Number>,
+    factor: Option<Number>,
+}
+
+fn values_of(field: &str, values: Value) -> Result<Vec<Value>, SweepError> {
+    let values = match values {
+        Value::Array(values) => values,
+        Value::Object(_) => serde_json::from_value::<Range>(values)
+            .map_err(|_| SweepError::InvalidValues(field.into()))?
+            .values(field)?,
+        _ => return Err(SweepError::InvalidValues(field.into())),
+    };
+
+    match values.len() {
+        0 => Err(SweepError::NoValues(field.into())),
+        len if len > MAX_VALUES => Err(SweepError::TooManyValues(field.into())),
+        _ => Ok(values),
+    }
+}
+
+impl Range {
+    fn values(&self, field: &str) -> Result<Vec<Value>, SweepError> {
+        let invalid = |reason| SweepError::InvalidRange {
+            field: field.into(),
+            reason,
+        };
+        // Integer ranges produce integers, since most fields are sizes.
+        let integers = [
+            Some(&self.start),
+            Some(&self.end),
+            self.step.as_ref(),
+            self.factor.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .all(|number| number.is_u64());
+        let as_f64 = |number: &Number| number.as_f64().unwrap_or(f64::NAN);
+        let (start, end) = (as_f64(&self.start), as_f64(&self.end));
+
+        let next: Box<dyn Fn(usize) -> f64> = match (&self.step, &self.factor) {
+            (Some(_), Some(_)) => return Err(invalid("either a step or a factor is expected")),
+            (None, None) => return Err(invalid("a step or a factor is expected")),
+            (Some(step), None) => {
+                let step = as_f64(step);
+                if step.is_nan() || step <= 0.0 {
+                    return Err(invalid("the step must be positive"));
+                }
+                Box::new(move |i| start + i as f64 * step)
+            }
+            (None, Some(factor)) => {
+                let factor = as_f64(factor);
+                if factor.is_nan() || factor <= 1.0 || start <= 0.0 {
+                    return Err(invalid(
+                        "the factor must be greater than one and the start positive",
+                    ));
+                }
+                Box::new(move |i| start * factor.powi(i as i32))
+            }
+        };
+
+        // Removes the rounding errors of the floating point ranges, e.g. `0.30000000000000004`.
+        let round = |value: f64| (value * 1e9).round() / 1e9;
+        let values = (0..)
+            .map(|i| round(next(i)))
+            .take_while(|value| *value <= end)
+            // One more than the maximum, so the range is rejected instead of truncated.
+            .take(MAX_VALUES + 1)
+            .map(|value| match integers {
+                true => Value::from(value as u64),
+                false => Value::from(value),
+            })
+            .collect();
+
+        Ok(values)
+    }
+}
+
+/// All the combinations of the values of each field.
+fn product(axes: &[(String, Vec<Value>)]) -> Vec<Vec<&Value>> {
+    axes.iter()
+        .fold(vec![Vec::new()], |combinations, (_, values)| {
+            combinations
+                .iter()
+                .flat_map(|combination| {
+                    values.iter().map(move |value| {
+                        let mut combination = combination.clone();
+                        combination.push(value);
+                        combination
+                    })
+                })
+                .collect()
+        })
+}
+
+/// Set the field at the dotted path, creating the missing intermediate tables.
+fn set_field(config: &mut Value, path: &str, value: Value) -> Result<(), SweepError> {
+    let invalid = || SweepError::InvalidField(path.into());
+    let mut fields = path.split('.').peekable();
+    let mut table = config.as_object_mut().ok_or_else(invalid)?;
+
+    while let Some(field) = fields.next() {
+        if fields.peek().is_none() {
+            table.insert(field.into(), value);
+            return Ok(());
+        }
+
+        table = table
+            .entry(field)
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(invalid)?;
+    }
+
+    Err(invalid())
+}
+
+/// Format the value as it appears in the labels, which must not contain any `/`.
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.replace('/', "-"),
+        value => value.to_string().replace('/', "-"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn values(sweep: Value) -> Vec<Value> {
+        expand(&[json!({ "sweep": { "size": sweep } })])
+            .unwrap()
+            .into_iter()
+            .map(|(_, config)| config["size"].clone())
+            .collect()
+    }
+
+    #[test]
+    fn entry_without_sweep() {
+        let entry = json!({ "batch_size": 32 });
+
+        let cases = expand(std::slice::from_ref(&entry)).unwrap();
+
+        assert_eq!(cases, vec![(None, entry)]);
+    }
+
+    #[test]
+    fn list_sweep() {
+        assert_eq!(
+            values(json!([1, 32, 512])),
+            vec![json!(1), json!(32), json!(512)]
+        );
+    }
+
+    #[test]
+    fn step_range() {
+        assert_eq!(
+            values(json!({ "start": 8, "end": 32, "step": 8 })),
+            vec![json!(8), json!(16), json!(24), json!(32)]
+        );
+        assert_eq!(
+            values(json!({ "start": 8, "end": 30, "step": 8 })),
+            vec![json!(8), json!(16), json!(24)]
+        );
+        assert_eq!(
+            values(json!({ "start": 0.1, "end": 0.3, "step": 0.1 })),
+            vec![json!(0.1), json!(0.2), json!(0.3)]
+        );
+    }
+
+    #[test]
+    fn factor_range_includes_end() {
+        assert_eq!(
+            values(json!({ "start": 64, "end": 1024, "factor": 2 })),
+            vec![json!(64), json!(128), json!(256), json!(512), json!(1024)]
+        );
+        assert_eq!(
+            values(json!({ "start": 64, "end": 1000, "factor": 2 })),
+            vec![json!(64), json!(128), json!(256), json!(512)]
+        );
+    }
+
+    #[test]
+    fn fractional_factor_range() {
+        assert_eq!(
+            values(json!({ "start": 2, "end": 20, "factor": 1.5 })),
+            vec![
+                json!(2.0),
+                json!(3.0),
+                json!(4.5),
+                json!(6.75),
+                json!(10.125),
+                json!(15.1875)
+            ]
+        );
+    }
+
+    #[test]
+    fn too_many_values() {
+        let expand_range = |range| expand(&[json!({ "sweep": { "size": range } })]);
+
+        assert!(matches!(
+            expand_range(json!({ "start": 0, "end": 1e12, "step": 1 })),
+            Err(SweepError::TooManyValues(field)) if field == "size"
+        ));
+        assert_eq!(
+            values(json!({ "start": 1, "end": MAX_VALUES, "step": 1 })).len(),
+            MAX_VALUES
+        );
+    }
+
+    #[test]
+    fn too_many_configs() {
+        let sizes: Vec<_> = (0..100).collect();
+        let entry = json!({ "sweep": { "a": sizes, "b": sizes } });
+
+        assert!(matches!(expand(&[entry]), Err(SweepError::TooManyConfigs)));
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        let expand_range = |range| expand(&[json!({ "sweep": { "size": range } })]);
+
+        assert!(matches!(
+            expand_range(json!({ "start": 1, "end": 8 })),
+            Err(SweepError::InvalidRange { .. })
+        ));
+        assert!(matches!(
+            expand_range(json!({ "start": 1, "end": 8, "step": 1, "factor": 2 })),
+            Err(SweepError::InvalidRange { .. })
+        ));
+        assert!(matches!(
+            expand_range(json!({ "start": 1, "end": 8, "factor": 1 })),
+            Err(SweepError::InvalidRange { .. })
+        ));
+        assert!(matches!(
+            expand_range(json!({ "start": 16, "end": 8, "step": 1 })),
+            Err(SweepError::NoValues(_))
+        ));
+        assert!(matches!(
+            expand_range(json!("large")),
+            Err(SweepError::InvalidValues(_))
+        ));
+    }
+
+    #[test]
+    fn cartesian_product_with_labels() {
+        let entry = json!({
+            "num_layers": 4,
+            "sweep": {
+                "batch_size": [1, 32],
+                "d_model": { "start": 64, "end": 256, "factor": 4 },
+            },
+        });
+
+        let cases = expand(&[entry]).unwrap();
+
+        assert_eq!(
+            cases,
+            vec![
+                (
+                    Some("batch_size=1,d_model=64".into()),
+                    json!({ "num_layers": 4, "batch_size": 1, "d_model": 64 })
+                ),
+                (
+                    Some("batch_size=1,d_model=256".into()),
+                    json!({ "num_layers": 4, "batch_size": 1, "d_model": 256 })
+                ),
+                (
+                    Some("batch_size=32,d_model=64".into()),
+                    json!({ "num_layers": 4, "batch_size": 32, "d_model": 64 })
+                ),
+                (
+                    Some("batch_size=32,d_model=256".into()),
+                    json!({ "num_layers": 4, "batch_size": 32, "d_model": 256 })
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn dotted_path_on_nested_structs() {
+        let entry = json!({
+            "batch_size": 8,
+            "conv2d": { "channels": [64, 64], "stride": [1, 1] },
+            "sweep": {
+                "conv2d.stride": [[1, 1], [2, 2]],
+                "encoder.attention.n_heads": ["a/b"],
+            },
+        });
+
+        let cases = expand(&[entry]).unwrap();
+
+        assert_eq!(
+            cases[1],
+            (
+                Some("conv2d.stride=[2,2],encoder.attention.n_heads=a-b".into()),
+                json!({
+                    "batch_size": 8,
+                    "conv2d": { "channels": [64, 64], "stride": [2, 2] },
+                    "encoder": { "attention": { "n_heads": "a/b" } },
+                })
+            )
+        );
+    }
+
+    #[test]
+    fn dotted_path_through_a_value() {
+        let entry = json!({ "batch_size": 8, "sweep": { "batch_size.value": [1] } });
+
+        assert!(matches!(
+            expand(&[entry]),
+            Err(SweepError::InvalidField(field)) if field == "batch_size.value"
+        ));
+    }
+}
@@ -1,28 +1,86 @@
-use crate::{bench::BenchSuite, conv2d, mlp, transformer};
+use crate::config_file::{ConfigFile, ConfigFileError};
+use crate::{
+    attention, bench::BenchSuite, conv1d, conv2d, conv_transpose2d, data_movement, decoder,
+    elementwise, matmul, mlp, models, norm, optimizer, pool2d, record, recurrent, reduction,
+    stable_hash, training, transformer,
+};
 use nvml_wrapper::Nvml;
-use std::{fs::File, io::Write};
 use sysinfo::{CpuExt, System, SystemExt};
 
-pub fn make_tables() {
-    let mut file = File::create("tables.toml").unwrap();
-    file.write("[top_comments]\n".to_string().as_bytes())
-        .unwrap();
+/// A benchmark suite as displayed in the reports.
+pub struct SuiteInfo {
+    pub name: String,
+    pub details: String,
+    /// Name of the elements processed by the benchmarks, FLOPs when none.
+    pub element: Option<&'static str>,
+}
 
-    write_section(
-        &mut file,
-        "Overview",
-        format!("Burn micro benchmarks\n{}", system_infos()).as_str(),
-    );
+impl SuiteInfo {
+    fn new<B: BenchSuite>() -> Self {
+        Self {
+            name: B::name(),
+            details: B::details(),
+            element: B::element(),
+        }
+    }
+}
+
+/// All the benchmark suites of the crate.
+pub fn suites() -> Vec<SuiteInfo> {
+    vec![
+        SuiteInfo::new::<mlp::MlpBenchSuite>(),
+        SuiteInfo::new::<transformer::TransformerBenchSuite>(),
+        SuiteInfo::new::<conv2d::Conv2dBenchSuite>(),
+        SuiteInfo::new::<matmul::MatmulBenchSuite>(),
+        SuiteInfo::new::<attention::AttentionBenchSuite>(),
+        SuiteInfo::new::<decoder::DecoderBenchSuite>(),
+        SuiteInfo::new::<recurrent::RecurrentBenchSuite>(),
+        SuiteInfo::new::<norm::NormBenchSuite>(),
+        SuiteInfo::new::<elementwise::ElementwiseBenchSuite>(),
+        SuiteInfo::new::<reduction::ReductionBenchSuite>(),
+        SuiteInfo::new::<data_movement::DataMovementBenchSuite>(),
+        SuiteInfo::new::<conv1d::Conv1dBenchSuite>(),
+        SuiteInfo::new::<conv_transpose2d::ConvTranspose2dBenchSuite>(),
+        SuiteInfo::new::<pool2d::Pool2dBenchSuite>(),
+        SuiteInfo::new::<models::ModelsBenchSuite>(),
+        SuiteInfo::new::<training::TrainingBenchSuite>(),
+        SuiteInfo::new::<optimizer::OptimizerBenchSuite>(),
+        SuiteInfo::new::<record::RecordBenchSuite>(),
+    ]
+}
 
-    file.write("[table_comments]\n".to_string().as_bytes())
-        .unwrap();
+/// Check that the file only lists existing suites, with valid and supported configs.
+pub fn check_configs(file: &ConfigFile) -> Result<(), ConfigFileError> {
+    let names: Vec<_> = suites().into_iter().map(|suite| suite.name).collect();
 
-    write_bench_suite::<mlp::MlpBenchSuite>(&mut file);
-    write_bench_suite::<transformer::TransformerBenchSuite>(&mut file);
-    write_bench_suite::<conv2d::Conv2dBenchSuite>(&mut file);
+    if let Some(suite) = file
+        .suites()
+        .find(|suite| !names.iter().any(|name| name == suite))
+    {
+        return Err(ConfigFileError::UnknownSuite(suite.into()));
+    }
+
+    check::<mlp::MlpBenchSuite>(file)?;
+    check::<transformer::TransformerBenchSuite>(file)?;
+    check::<conv2d::Conv2dBenchSuite>(file)?;
+    check::<matmul::MatmulBenchSuite>(file)?;
+    check::<attention::AttentionBenchSuite>(file)?;
+    check::<decoder::DecoderBenchSuite>(file)?;
+    check::<recurrent::RecurrentBenchSuite>(file)?;
+    check::<norm::NormBenchSuite>(file)?;
+    check::<elementwise::ElementwiseBenchSuite>(file)?;
+    check::<reduction::ReductionBenchSuite>(file)?;
+    check::<data_movement::DataMovementBenchSuite>(file)?;
+    check::<conv1d::Conv1dBenchSuite>(file)?;
+    check::<conv_transpose2d::ConvTranspose2dBenchSuite>(file)?;
+    check::<pool2d::Pool2dBenchSuite>(file)?;
+    check::<models::ModelsBenchSuite>(file)?;
+    check::<training::TrainingBenchSuite>(file)?;
+    check::<optimizer::OptimizerBenchSuite>(file)?;
+    check::<record::RecordBenchSuite>(file)
 }
 
-fn system_infos() -> String {
+pub fn system_infos() -> String {
     let sys = System::new_all();
     let mut info = String::from("\n## System\n\n");
 
@@ -35,33 +93,154 @@ fn system_infos() -> String {
     let cpu = sys.global_cpu_info();
     info += format!("- CPU: {} {}\n", cpu.brand(), cpu.name()).as_str();
 
-    if let Ok(nvml) = Nvml::init() {
-        if let Ok(count) = nvml.device_count() {
-            for index in 0..count {
-                if let Ok(device) = nvml.device_by_index(index) {
-                    match count {
-                        1 => info += "- GPU: ",
-                        _ => info += format!("- GPU({index}): ").as_str(),
-                    };
-                    info += format!("{:?} {}", device.brand().unwrap(), device.name().unwrap())
-                        .as_str();
-                }
-            }
-        }
+    let gpus = gpus();
+    for (index, gpu) in gpus.iter().enumerate() {
+        match gpus.len() {
+            1 => info += "- GPU: ",
+            _ => info += format!("- GPU({index}): ").as_str(),
+        };
+        info += format!("{gpu}\n").as_str();
     }
 
     info
 }
 
-fn write_bench_suite<B: BenchSuite>(file: &mut File) {
-    file.write(format!("{} = \"\"\"\n", B::name()).as_bytes())
-        .unwrap();
-    file.write(B::details().as_bytes()).unwrap();
-    file.write("\n\"\"\"\n".to_string().as_bytes()).unwrap();
+/// Check that the configs of the suite listed by the file are valid and supported.
+fn check<B: BenchSuite>(file: &ConfigFile) -> Result<(), ConfigFileError> {
+    let configs = file.configs::<B::Config>(&B::name())?.unwrap_or_default();
+
+    configs.into_iter().try_for_each(|(label, config)| {
+        B::validate(&config).map_err(|reason| ConfigFileError::UnsupportedConfig {
+            suite: B::name(),
+            label: label.unwrap_or_else(|| B::label(&config)),
+            reason,
+        })
+    })
 }
 
-fn write_section(file: &mut File, name: &str, content: &str) {
-    file.write(format!("{name} = \"\"\"\n").as_bytes()).unwrap();
-    file.write(content.as_bytes()).unwrap();
-    file.write("\n\"\"\"\n".to_string().as_bytes()).unwrap();
+/// Fingerprint identifying the machine, derived from its hardware: the CPU, its number of cores,
+/// the memory and the GPUs.
+///
+/// The OS and kernel versions are left out, so the history of a machine isn't split by its
+/// updates.
+pub fn machine_fingerprint() -> String {
+    let sys = System::new_all();
+    let cpu = sys.global_cpu_info();
+    let mut hardware = format!(
+        "{} {}\n{:?}\n{}\n",
+        cpu.brand(),
+        cpu.name(),
+        sys.physical_core_count(),
+        sys.total_memory()
+    );
+    gpus()
+        .iter()
+        .for_each(|gpu| hardware += format!("{gpu}\n").as_str());
+
+    format!("{:016x}", stable_hash(hardware.as_bytes()))
+}
+
+/// Brand and name of each GPU found by NVML.
+fn gpus() -> Vec<String> {
+    let Ok(nvml) = Nvml::init() else {
+        return Vec::new();
+    };
+    let count = nvml.device_count().unwrap_or(0);
+
+    (0..count)
+        .filter_map(|index| nvml.device_by_index(index).ok())
+        .map(|device| format!("{:?} {}", device.brand().unwrap(), device.name().unwrap()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn::{config::Config, tensor::backend::Backend};
+    use criterion::Criterion;
+    use tempfile::TempDir;
+
+    struct EvenBenchSuite;
+
+    #[derive(Config)]
+    struct EvenConfig {
+        size: usize,
+    }
+
+    impl BenchSuite for EvenBenchSuite {
+        type Config = EvenConfig;
+
+        fn name() -> String {
+            "even".into()
+        }
+
+        fn description() -> String {
+            "Even sizes only.".into()
+        }
+
+        fn default_configs() -> Vec<Self::Config> {
+            vec![EvenConfig::new(2)]
+        }
+
+        fn label(config: &Self::Config) -> String {
+            format!("s{}", config.size)
+        }
+
+        fn validate(config: &Self::Config) -> Result<(), String> {
+            match config.size % 2 {
+                0 => Ok(()),
+                _ => Err("the size must be even".into()),
+            }
+        }
+
+        fn run_backend<B: Backend>(_c: &mut Criterion, _device: &B::Device) {}
+    }
+
+    fn load(content: &str) -> (TempDir, ConfigFile) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("configs.toml");
+        std::fs::write(&path, content).unwrap();
+        let file = ConfigFile::load(path).unwrap();
+
+        (dir, file)
+    }
+
+    #[test]
+    fn unknown_suite() {
+        let (_dir, file) =
+            load("[[mlp]]\nbatch_size = 1\nnum_layers = 1\nd_model = 8\n\n[[mpl]]\n");
+
+        assert!(matches!(
+            check_configs(&file),
+            Err(ConfigFileError::UnknownSuite(suite)) if suite == "mpl"
+        ));
+    }
+
+    #[test]
+    fn supported_configs() {
+        let (_dir, file) = load("[[even]]\nsize = 4\n");
+
+        assert!(check::<EvenBenchSuite>(&file).is_ok());
+    }
+
+    #[test]
+    fn unsupported_configs() {
+        let (_dir, file) = load("[[even]]\nsize = 4\n\n[[even]]\nsize = 3\n");
+
+        assert!(matches!(
+            check::<EvenBenchSuite>(&file),
+            Err(ConfigFileError::UnsupportedConfig { suite, label, .. })
+                if suite == "even" && label == "s3"
+        ));
+    }
+
+    #[test]
+    fn unsupported_swept_configs_are_reported_by_their_sweep() {
+        let (_dir, file) = load("[[even]]\n\n[even.sweep]\nsize = [2, 5]\n");
+
+        assert!(matches!(
+            check::<EvenBenchSuite>(&file),
+            Err(ConfigFileError::UnsupportedConfig { label, .. }) if label == "size=5"
+        ));
+    }
 }
@@ -0,0 +1,157 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use toml::{Table, Value};
+
+static WORK_DIR: &str = "target/burn_benches/work";
+
+/// Where the benchmarked burn crates come from.
+pub enum BurnSource<'a> {
+    /// A git repository with the kind of reference (`tag`, `rev` or `branch`) and its value.
+    Git {
+        repo: &'a str,
+        reference: &'a str,
+        value: &'a str,
+    },
+    /// A local checkout of the burn repository.
+    Path(&'a str),
+}
+
+/// A scratch crate benchmarking the sources of this crate against a specific burn source.
+///
+/// Each workspace has its own manifest, lock file and target directory, so the repository is
+/// never modified and runs for different burn revisions can coexist.
+pub struct Workspace {
+    dir: PathBuf,
+}
+
+impl Workspace {
+    pub fn new(name: &str) -> Self {
+        Self {
+            dir: Path::new(WORK_DIR).join(name),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write the manifest of the workspace with every burn crate pointing to the given source.
+    ///
+    /// The lock file of a previous run is removed, so branches are resolved to their latest commit.
+    pub fn create(&self, source: &BurnSource) -> io::Result<()> {
+        std::fs::create_dir_all(self.dir.join("target/tmp"))?;
+        std::fs::write(self.dir.join("Cargo.toml"), manifest(source)?)?;
+
+        match std::fs::remove_file(self.dir.join("Cargo.lock")) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// The commit of burn the workspace was built with, if it can be resolved.
+    ///
+    /// Git sources are resolved from the lock file, so the workspace must be built first.
+    pub fn revision(&self, source: &BurnSource) -> Option<String> {
+        match source {
+            BurnSource::Git { .. } => {
+                let lock: Table = std::fs::read_to_string(self.dir.join("Cargo.lock"))
+                    .ok()?
+                    .parse()
+                    .ok()?;
+
+                lock.get("package")?
+                    .as_array()?
+                    .iter()
+                    .filter_map(Value::as_table)
+                    .filter(|package| package.get("name").and_then(Value::as_str) == Some("burn"))
+                    .find_map(|package| package.get("source")?.as_str()?.split_once('#'))
+                    .map(|(_, commit)| commit.to_string())
+            }
+            BurnSource::Path(path) => {
+                let output = Command::new("git")
+                    .args(["-C", path, "rev-parse", "HEAD"])
+                    .output()
+                    .ok()?;
+
+                match output.status.success() {
+                    true => Some(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+                    false => None,
+                }
+            }
+        }
+    }
+}
+
+/// Build the workspace manifest from the one of this crate, with all targets pointing to the
+/// sources of this crate.
+fn manifest(source: &BurnSource) -> io::Result<String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut manifest: Table = std::fs::read_to_string(root.join("Cargo.toml"))?
+        .parse()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    if let Some(Value::Table(package)) = manifest.get_mut("package") {
+        package.insert("autobins".into(), false.into());
+        package.insert("autobenches".into(), false.into());
+    }
+
+    if let Some(Value::Table(dependencies)) = manifest.get_mut("dependencies") {
+        for (name, dependency) in dependencies.iter_mut() {
+            match dependency {
+                Value::Table(dependency) if name.starts_with("burn") => {
+                    set_source(name, dependency, source)?
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(Value::Array(benches)) = manifest.get_mut("bench") {
+        for bench in benches.iter_mut() {
+            if let Value::Table(bench) = bench {
+                let path = match bench.get("name") {
+                    Some(Value::String(name)) => root.join("benches").join(format!("{name}.rs")),
+                    _ => continue,
+                };
+                bench.insert("path".into(), path.display().to_string().into());
+            }
+        }
+    }
+
+    let mut lib = Table::new();
+    lib.insert("name".into(), "burn_benches".into());
+    lib.insert(
+        "path".into(),
+        root.join("src/lib.rs").display().to_string().into(),
+    );
+    manifest.insert("lib".into(), lib.into());
+
+    // Prevents cargo from looking for a parent workspace in this repository.
+    manifest.insert("workspace".into(), Table::new().into());
+
+    toml::to_string(&manifest).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn set_source(name: &str, dependency: &mut Table, source: &BurnSource) -> io::Result<()> {
+    for key in ["git", "tag", "rev", "branch", "path"] {
+        dependency.remove(key);
+    }
+
+    match source {
+        BurnSource::Git {
+            repo,
+            reference,
+            value,
+        } => {
+            dependency.insert("git".into(), (*repo).into());
+            dependency.insert((*reference).into(), (*value).into());
+        }
+        BurnSource::Path(path) => {
+            let path = Path::new(path).join(name).canonicalize()?;
+            dependency.insert("path".into(), path.display().to_string().into());
+        }
+    }
+
+    Ok(())
+}